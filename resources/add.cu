@@ -4,4 +4,14 @@ extern "C" __global__ void sum(const float* x, const float* y, float* out, int c
     for (int i = blockIdx.x * blockDim.x + threadIdx.x; i < count; i += blockDim.x * gridDim.x) {
         out[i] = x[i] + y[i];
     }
-}
\ No newline at end of file
+}
+
+extern "C" __global__ void read_constant(int* out) {
+    *out = my_constant;
+}
+
+extern "C" __global__ void count(unsigned int* counter, int n) {
+    if (blockIdx.x * blockDim.x + threadIdx.x < n) {
+        atomicAdd(counter, 1);
+    }
+}
@@ -0,0 +1,13 @@
+extern "C" __global__ void reverse(const float* in, float* out) {
+    extern __shared__ float buffer[];
+    unsigned int i = threadIdx.x;
+    buffer[i] = in[i];
+    __syncthreads();
+    out[i] = buffer[blockDim.x - 1 - i];
+}
+
+extern "C" __global__ void dynamic_smem_size(unsigned int* out) {
+    unsigned int size;
+    asm("mov.u32 %0, %%dynamic_smem_size;" : "=r"(size));
+    *out = size;
+}
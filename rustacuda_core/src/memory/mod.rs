@@ -47,6 +47,15 @@ use core::num::*;
 /// unsafe impl DeviceCopy for MyStruct { }
 /// ```
 ///
+/// ## What about `bytemuck::Pod`?
+///
+/// Every type which implements `bytemuck::Pod` also meets the requirements of `DeviceCopy`, but
+/// `DeviceCopy` can't be implemented for all `Pod` types at once, since that would conflict with
+/// the implementations for primitives, arrays and tuples above. If a type already derives `Pod`,
+/// also derive `DeviceCopy` for it; both derives check the fields independently. Prefer the
+/// `DeviceCopy` derive on its own for types which are never viewed as bytes on the host, since
+/// `Pod` additionally forbids padding and requires `#[repr(C)]` or `#[repr(transparent)]`.
+///
 /// ## What is the difference between `DeviceCopy` and `Copy`?
 ///
 /// `DeviceCopy` is stricter than `Copy`. `DeviceCopy` must only be implemented for types which
@@ -55,6 +64,14 @@ use core::num::*;
 /// assignment the way that `Copy` values are. This is helpful, as it may be desirable to implement
 /// `DeviceCopy` for large structures that would be inefficient to copy for every assignment.
 ///
+/// ## Can I pass tuples to kernels?
+///
+/// `DeviceCopy` is implemented for tuples of up to twelve `DeviceCopy` elements, which is
+/// convenient for data which only the host interprets. However, Rust does not guarantee the
+/// layout of tuples: the elements may be reordered or padded differently from any C struct. For
+/// values which kernels read, such as kernel parameters, use a `#[repr(C)]` struct which matches
+/// the struct declared in the device code.
+///
 /// ## When can't my type be `DeviceCopy`?
 ///
 /// Some types cannot be safely copied to the device. For example, copying `&T` would create an
@@ -86,55 +103,114 @@ unsafe impl<L: DeviceCopy, R: DeviceCopy> DeviceCopy for Result<L, R> {}
 unsafe impl<T: ?Sized + DeviceCopy> DeviceCopy for PhantomData<T> {}
 unsafe impl<T: DeviceCopy> DeviceCopy for Wrapping<T> {}
 
-macro_rules! impl_device_copy_array {
-    ($($n:expr)*) => {
+unsafe impl<T: DeviceCopy, const N: usize> DeviceCopy for [T; N] {}
+unsafe impl DeviceCopy for () {}
+
+macro_rules! impl_device_copy_tuple {
+    ($($name:ident)+) => {
+        unsafe impl<$($name: DeviceCopy),+> DeviceCopy for ($($name,)+) {}
+    };
+}
+impl_device_copy_tuple!(A);
+impl_device_copy_tuple!(A B);
+impl_device_copy_tuple!(A B C);
+impl_device_copy_tuple!(A B C D);
+impl_device_copy_tuple!(A B C D E);
+impl_device_copy_tuple!(A B C D E F);
+impl_device_copy_tuple!(A B C D E F G);
+impl_device_copy_tuple!(A B C D E F G H);
+impl_device_copy_tuple!(A B C D E F G H I);
+impl_device_copy_tuple!(A B C D E F G H I J);
+impl_device_copy_tuple!(A B C D E F G H I J K);
+impl_device_copy_tuple!(A B C D E F G H I J K L);
+
+/// Marker trait for types for which a value with every byte set to zero is valid.
+///
+/// This is used by functions such as `DeviceBuffer::zeroed` and `DeviceBox::zeroed`, which fill
+/// the allocated memory with zeroes and then expose it as initialized values of type `T`.
+///
+/// ## How can I implement Zeroable?
+///
+/// As with `DeviceCopy`, the simplest way is to use `derive`:
+///
+/// ```
+/// #[macro_use]
+/// extern crate rustacuda;
+///
+/// #[derive(Clone, DeviceCopy, Zeroable)]
+/// struct MyStruct {
+///     x: u64,
+///     y: f32,
+/// }
+///
+/// # fn main() {}
+/// ```
+///
+/// The `Zeroable` derive macro checks that every field of the struct or union is `Zeroable`. It
+/// cannot be used on enums, since the all-zeroes bit pattern is not necessarily a valid
+/// discriminant. For example, this fails to compile because `NonZeroU32` can never be zero:
+///
+/// ```compile_fail
+/// # #[macro_use]
+/// # extern crate rustacuda;
+/// use std::num::NonZeroU32;
+///
+/// #[derive(Clone, DeviceCopy, Zeroable)]
+/// struct MyStruct(NonZeroU32);
+/// # fn main() {}
+/// ```
+///
+/// You can also implement `Zeroable` unsafely:
+///
+/// ```
+/// use rustacuda::memory::Zeroable;
+///
+/// #[derive(Clone)]
+/// struct MyStruct(u64);
+///
+/// unsafe impl Zeroable for MyStruct { }
+/// ```
+pub unsafe trait Zeroable {
+    // Empty
+}
+
+macro_rules! impl_zeroable {
+    ($($t:ty)*) => {
         $(
-            unsafe impl<T: DeviceCopy> DeviceCopy for [T;$ n] {}
+            unsafe impl Zeroable for $t {}
         )*
     }
 }
 
-impl_device_copy_array! {
-    1 2 3 4 5 6 7 8 9 10
-    11 12 13 14 15 16 17 18 19 20
-    21 22 23 24 25 26 27 28 29 30
-    31 32
-}
-unsafe impl DeviceCopy for () {}
-unsafe impl<A: DeviceCopy, B: DeviceCopy> DeviceCopy for (A, B) {}
-unsafe impl<A: DeviceCopy, B: DeviceCopy, C: DeviceCopy> DeviceCopy for (A, B, C) {}
-unsafe impl<A: DeviceCopy, B: DeviceCopy, C: DeviceCopy, D: DeviceCopy> DeviceCopy
-    for (A, B, C, D)
-{
-}
-unsafe impl<A: DeviceCopy, B: DeviceCopy, C: DeviceCopy, D: DeviceCopy, E: DeviceCopy> DeviceCopy
-    for (A, B, C, D, E)
-{
-}
-unsafe impl<A: DeviceCopy, B: DeviceCopy, C: DeviceCopy, D: DeviceCopy, E: DeviceCopy, F: DeviceCopy>
-    DeviceCopy for (A, B, C, D, E, F)
-{
-}
-unsafe impl<
-        A: DeviceCopy,
-        B: DeviceCopy,
-        C: DeviceCopy,
-        D: DeviceCopy,
-        E: DeviceCopy,
-        F: DeviceCopy,
-        G: DeviceCopy,
-    > DeviceCopy for (A, B, C, D, E, F, G)
-{
-}
-unsafe impl<
-        A: DeviceCopy,
-        B: DeviceCopy,
-        C: DeviceCopy,
-        D: DeviceCopy,
-        E: DeviceCopy,
-        F: DeviceCopy,
-        G: DeviceCopy,
-        H: DeviceCopy,
-    > DeviceCopy for (A, B, C, D, E, F, G, H)
-{
+impl_zeroable!(
+    usize u8 u16 u32 u64 u128
+    isize i8 i16 i32 i64 i128
+    f32 f64
+    bool char
+
+    Option<NonZeroU8> Option<NonZeroU16> Option<NonZeroU32> Option<NonZeroU64> Option<NonZeroU128>
+);
+unsafe impl<T: ?Sized> Zeroable for PhantomData<T> {}
+unsafe impl<T: Zeroable> Zeroable for Wrapping<T> {}
+
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
+
+macro_rules! impl_zeroable_tuple {
+    ($($name:ident)+) => {
+        unsafe impl<$($name: Zeroable),+> Zeroable for ($($name,)+) {}
+    }
 }
+
+unsafe impl Zeroable for () {}
+impl_zeroable_tuple!(A);
+impl_zeroable_tuple!(A B);
+impl_zeroable_tuple!(A B C);
+impl_zeroable_tuple!(A B C D);
+impl_zeroable_tuple!(A B C D E);
+impl_zeroable_tuple!(A B C D E F);
+impl_zeroable_tuple!(A B C D E F G);
+impl_zeroable_tuple!(A B C D E F G H);
+impl_zeroable_tuple!(A B C D E F G H I);
+impl_zeroable_tuple!(A B C D E F G H I J);
+impl_zeroable_tuple!(A B C D E F G H I J K);
+impl_zeroable_tuple!(A B C D E F G H I J K L);
@@ -1,4 +1,4 @@
-use crate::memory::DeviceCopy;
+use crate::memory::{DeviceCopy, Zeroable};
 use core::fmt;
 use core::ptr;
 
@@ -17,6 +17,7 @@ use core::ptr;
 #[derive(Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
 pub struct DevicePointer<T>(*mut T);
 unsafe impl<T> DeviceCopy for DevicePointer<T> {}
+unsafe impl<T> Zeroable for DevicePointer<T> {}
 impl<T> DevicePointer<T> {
     /// Returns a null device pointer.
     ///
@@ -323,6 +324,26 @@ impl<T> DevicePointer<T> {
     {
         self.wrapping_offset((count as isize).wrapping_neg())
     }
+
+    /// Casts this pointer to a pointer to a different type.
+    ///
+    /// This only reinterprets the address; it does not touch the device. The caller is
+    /// responsible for ensuring that the pointed-to memory is valid for type `U`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// unsafe {
+    ///     let dev_ptr = cuda_malloc::<f32>(5).unwrap();
+    ///     let bits_ptr: DevicePointer<u32> = dev_ptr.cast();
+    ///     cuda_free(dev_ptr).unwrap();
+    /// }
+    /// ```
+    pub fn cast<U>(self) -> DevicePointer<U> {
+        DevicePointer(self.0 as *mut U)
+    }
 }
 impl<T> fmt::Pointer for DevicePointer<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -350,6 +371,7 @@ impl<T> Copy for DevicePointer<T> {}
 #[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct UnifiedPointer<T: DeviceCopy>(*mut T);
 unsafe impl<T: DeviceCopy> DeviceCopy for UnifiedPointer<T> {}
+unsafe impl<T: DeviceCopy> Zeroable for UnifiedPointer<T> {}
 impl<T: DeviceCopy> UnifiedPointer<T> {
     /// Returns a null unified pointer.
     ///
@@ -656,6 +678,28 @@ impl<T: DeviceCopy> UnifiedPointer<T> {
     {
         self.wrapping_offset((count as isize).wrapping_neg())
     }
+
+    /// Casts this pointer to a pointer to a different type.
+    ///
+    /// This only reinterprets the address. The caller is responsible for ensuring that the
+    /// pointed-to memory is valid for type `U` before dereferencing the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// unsafe {
+    ///     let mut unified_ptr = cuda_malloc_unified::<f32>(1).unwrap();
+    ///     *unified_ptr.as_raw_mut() = 1.0;
+    ///     let mut bits_ptr: UnifiedPointer<u32> = unified_ptr.cast();
+    ///     assert_eq!(0x3f80_0000, *bits_ptr.as_raw_mut());
+    ///     cuda_free_unified(unified_ptr).unwrap();
+    /// }
+    /// ```
+    pub fn cast<U: DeviceCopy>(self) -> UnifiedPointer<U> {
+        UnifiedPointer(self.0 as *mut U)
+    }
 }
 impl<T: DeviceCopy> fmt::Pointer for UnifiedPointer<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
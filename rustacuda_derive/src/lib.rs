@@ -19,9 +19,14 @@ pub fn derive_device_copy(input: BaseTokenStream) -> BaseTokenStream {
     BaseTokenStream::from(gen)
 }
 
-fn impl_device_copy(input: &DeriveInput) -> TokenStream {
-    let input_type = &input.ident;
+#[proc_macro_derive(Zeroable)]
+pub fn derive_zeroable(input: BaseTokenStream) -> BaseTokenStream {
+    let ast = syn::parse(input).unwrap();
+    let gen = impl_zeroable(&ast);
+    BaseTokenStream::from(gen)
+}
 
+fn impl_device_copy(input: &DeriveInput) -> TokenStream {
     // Generate the code to type-check all fields of the derived struct/enum/union. We can't perform
     // type checking at expansion-time, so instead we generate a dummy nested function with a
     // type-bound on DeviceCopy and call it with every type that's in the struct/enum/union.
@@ -32,27 +37,51 @@ fn impl_device_copy(input: &DeriveInput) -> TokenStream {
         Data::Union(ref data_union) => type_check_union(data_union),
     };
 
+    impl_marker_trait(input, "DeviceCopy", check_types_code)
+}
+
+fn impl_zeroable(input: &DeriveInput) -> TokenStream {
+    // All-zeroes is only a valid value for a struct or union if it is valid for the fields. Enums
+    // are rejected outright, since zero is not necessarily a valid discriminant.
+    let check_types_code = match input.data {
+        Data::Struct(ref data_struct) => type_check_struct(data_struct),
+        Data::Enum(_) => panic!("Zeroable cannot be derived for enums"),
+        Data::Union(ref data_union) => type_check_union(data_union),
+    };
+
+    impl_marker_trait(input, "Zeroable", check_types_code)
+}
+
+fn impl_marker_trait(
+    input: &DeriveInput,
+    trait_name: &str,
+    check_types_code: TokenStream,
+) -> TokenStream {
+    let input_type = &input.ident;
+    let trait_ident = Ident::new(trait_name, Span::call_site());
+
     // We need a function for the type-checking code to live in, so generate a complicated and
     // hopefully-unique name for that
     let type_test_func_name = format!(
-        "__verify_{}_can_implement_DeviceCopy",
-        input_type.to_string()
+        "__verify_{}_can_implement_{}",
+        input_type.to_string(),
+        trait_name
     );
     let type_test_func_ident = Ident::new(&type_test_func_name, Span::call_site());
 
-    // If the struct/enum/union is generic, we need to add the DeviceCopy bound to the generics
-    // when implementing DeviceCopy.
-    let generics = add_bound_to_generics(&input.generics);
+    // If the struct/enum/union is generic, we need to add the trait bound to the generics
+    // when implementing the trait.
+    let generics = add_bound_to_generics(&input.generics, &trait_ident);
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
     // Finally, generate the unsafe impl and the type-checking function.
     let generated_code = quote! {
-        unsafe impl#impl_generics ::rustacuda_core::DeviceCopy for #input_type#type_generics #where_clause {}
+        unsafe impl#impl_generics ::rustacuda_core::#trait_ident for #input_type#type_generics #where_clause {}
 
         #[doc(hidden)]
         #[allow(all)]
         fn #type_test_func_ident#impl_generics(value: &#input_type#type_generics) #where_clause {
-            fn assert_impl<T: ::rustacuda_core::DeviceCopy>() {}
+            fn assert_impl<T: ::rustacuda_core::#trait_ident>() {}
             #check_types_code
         }
     };
@@ -60,10 +89,10 @@ fn impl_device_copy(input: &DeriveInput) -> TokenStream {
     TokenStream::from(generated_code)
 }
 
-fn add_bound_to_generics(generics: &Generics) -> Generics {
+fn add_bound_to_generics(generics: &Generics, trait_ident: &Ident) -> Generics {
     let mut new_generics = generics.clone();
     let bound: TypeParamBound =
-        parse_str(&quote! {::rustacuda_core::DeviceCopy}.to_string()).unwrap();
+        parse_str(&quote! {::rustacuda_core::#trait_ident}.to_string()).unwrap();
 
     for type_param in &mut new_generics.type_params_mut() {
         type_param.bounds.push(bound.clone())
@@ -11,6 +11,18 @@
 //! The top context in that stack is known as the "current" context and it is used in most CUDA
 //! API calls. One context can be safely made current in multiple CPU threads.
 //!
+//! There are two ways to change the current context.
+//! [`ContextStack::push`](struct.ContextStack.html#method.push) and
+//! [`ContextStack::pop`](struct.ContextStack.html#method.pop) grow and shrink the stack, so the
+//! previously-current context becomes current again after a pop. This suits code which
+//! temporarily switches to another context and then switches back.
+//! [`CurrentContext::set_current`](struct.CurrentContext.html#method.set_current) replaces the top
+//! of the stack instead, which suits threads that switch between several contexts (for example,
+//! one per GPU) and don't need to restore the previous one. Neither operation transfers ownership;
+//! [`CurrentContext::get_current`](struct.CurrentContext.html#method.get_current) likewise returns
+//! a non-owning [`UnownedContext`](struct.UnownedContext.html), so the context is still destroyed
+//! exactly once, when its owning `Context` is dropped.
+//!
 //! # Safety
 //!
 //! The CUDA context management API does not fit easily into Rust's safety guarantees.
@@ -325,6 +337,57 @@ impl Context {
         UnownedContext { inner: self.inner }
     }
 
+    /// Allow kernels and copies running in this context to access memory allocated in `peer`.
+    ///
+    /// Access is one-way; to let `peer` access this context's memory as well, call this function
+    /// on `peer` too. Use [`Device::can_access_peer`](../device/struct.Device.html#method.can_access_peer)
+    /// to check whether the two devices support peer access first.
+    ///
+    /// # Errors:
+    ///
+    /// Returns `PeerAccessAlreadyEnabled` if access was already enabled, `PeerAccessUnsupported`
+    /// if the devices can't access each other, or other errors from CUDA.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::device::Device;
+    /// # use rustacuda::context::{Context, ContextFlags, ContextStack};
+    /// # use std::error::Error;
+    /// #
+    /// # fn main () -> Result<(), Box<dyn Error>> {
+    /// # rustacuda::init(rustacuda::CudaFlags::empty())?;
+    /// # if Device::num_devices()? < 2 { return Ok(()); }
+    /// let device0 = Device::get_device(0)?;
+    /// let device1 = Device::get_device(1)?;
+    /// if device0.can_access_peer(device1)? {
+    ///     let context0 = Context::create_and_push(ContextFlags::SCHED_AUTO, device0)?;
+    ///     let context1 = Context::create_and_push(ContextFlags::SCHED_AUTO, device1)?;
+    ///     context0.enable_peer_access(&context1)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enable_peer_access<C: ContextHandle>(&self, peer: &C) -> CudaResult<()> {
+        ContextStack::push(self)?;
+        let result = unsafe { cuda::cuCtxEnablePeerAccess(peer.get_inner(), 0).to_result() };
+        let _ = ContextStack::pop()?;
+        result
+    }
+
+    /// Stop kernels and copies running in this context from accessing memory allocated in `peer`.
+    ///
+    /// # Errors:
+    ///
+    /// Returns `PeerAccessNotEnabled` if access was not enabled with
+    /// [`enable_peer_access`](#method.enable_peer_access), or other errors from CUDA.
+    pub fn disable_peer_access<C: ContextHandle>(&self, peer: &C) -> CudaResult<()> {
+        ContextStack::push(self)?;
+        let result = unsafe { cuda::cuCtxDisablePeerAccess(peer.get_inner()).to_result() };
+        let _ = ContextStack::pop()?;
+        result
+    }
+
     /// Destroy a `Context`, returning an error.
     ///
     /// Destroying a context can return errors from previous asynchronous work. This function
@@ -403,7 +466,9 @@ impl ContextHandle for UnownedContext {
 }
 
 /// Non-owning handle to a CUDA context.
-#[derive(Debug, Clone)]
+///
+/// Two handles compare equal if they refer to the same underlying context.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct UnownedContext {
     inner: CUcontext,
 }
@@ -515,6 +580,35 @@ pub struct StreamPriorityRange {
 #[derive(Debug)]
 pub struct CurrentContext;
 impl CurrentContext {
+    /// Get the API version used to create the current context.
+    ///
+    /// This is not necessarily the latest version supported by the driver.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::device::Device;
+    /// # use rustacuda::context::{ Context, ContextFlags, CurrentContext };
+    /// # use std::error::Error;
+    /// #
+    /// # fn main () -> Result<(), Box<dyn Error>> {
+    /// # rustacuda::init(rustacuda::CudaFlags::empty())?;
+    /// # let device = Device::get_device(0)?;
+    /// let context = Context::create_and_push(ContextFlags::MAP_HOST | ContextFlags::SCHED_AUTO, device)?;
+    /// let version = CurrentContext::get_api_version()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_api_version() -> CudaResult<CudaApiVersion> {
+        unsafe {
+            let mut api_version = 0u32;
+            cuda::cuCtxGetApiVersion(ptr::null_mut(), &mut api_version as *mut u32).to_result()?;
+            Ok(CudaApiVersion {
+                version: api_version as i32,
+            })
+        }
+    }
+
     /// Returns the preferred cache configuration for the current context.
     ///
     /// On devices where the L1 cache and shared memory use the same hardware resources, this
@@ -719,16 +813,17 @@ impl CurrentContext {
     /// Note that this is only a request; the driver is free to modify the requested value to meet
     /// hardware requirements. Each limit has some specific restrictions.
     ///
-    /// * `StackSize`: Controls the stack size in bytes for each GPU thread
+    /// * `StackSize`: Controls the stack size in bytes for each GPU thread. Kernels which recurse
+    ///   deeply may need a larger stack than the default, or they fail when a thread overflows it.
     /// * `PrintfFifoSize`: Controls the size in bytes of the FIFO used by the `printf()` device
     ///   system call. This cannot be changed after a kernel has been launched which uses the
     ///   `printf()` function.
     /// * `MallocHeapSize`: Controls the size in bytes of the heap used by the `malloc()` and `free()`
-    ///   device system calls. This cannot be changed aftr a kernel has been launched which uses the
+    ///   device system calls. This cannot be changed after a kernel has been launched which uses the
     ///   `malloc()` and `free()` system calls.
-    /// * `DeviceRuntimeSyncDepth`: Controls the maximum nesting depth of a grid at which a thread
-    ///   can safely call `cudaDeviceSynchronize()`. This cannot be changed after a kernel has been
-    ///   launched which uses the device runtime. When setting this limit, keep in mind that
+    /// * `DeviceRuntimeSynchronizeDepth`: Controls the maximum nesting depth of a grid at which a
+    ///   thread can safely call `cudaDeviceSynchronize()`. This cannot be changed after a kernel
+    ///   has been launched which uses the device runtime. When setting this limit, keep in mind that
     ///   additional levels of sync depth require the driver to reserve large amounts of device
     ///   memory which can no longer be used for device allocations.
     /// * `DeviceRuntimePendingLaunchCount`: Controls the maximum number of outstanding device
@@ -799,6 +894,7 @@ impl CurrentContext {
     /// # let device = Device::get_device(0)?;
     /// let context = Context::create_and_push(ContextFlags::MAP_HOST | ContextFlags::SCHED_AUTO, device)?;
     /// let unowned = CurrentContext::get_current()?;
+    /// assert_eq!(context.get_unowned(), unowned);
     /// # Ok(())
     /// # }
     /// ```
@@ -839,6 +935,38 @@ impl CurrentContext {
     }
 
     /// Block to wait for a context's tasks to complete.
+    ///
+    /// This waits for all work queued on every stream in the current context, including
+    /// non-blocking streams, so it can be used to drain outstanding work before shutting down.
+    /// Streams belonging to other contexts are not affected; to wait for those, make each context
+    /// current in turn and call this function.
+    ///
+    /// This is a coarse barrier. It waits for unrelated work as well, and leaves the device idle
+    /// until the host queues more work, so it can cost a lot more than waiting on just the work
+    /// that's needed. Prefer [`Stream::synchronize`](../stream/struct.Stream.html#method.synchronize)
+    /// or events in performance-sensitive code.
+    ///
+    /// # Errors:
+    ///
+    /// If any of the context's work failed, returns the error from that work.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::context::CurrentContext;
+    /// # use rustacuda::prelude::*;
+    /// # use std::error::Error;
+    /// #
+    /// # fn main () -> Result<(), Box<dyn Error>> {
+    /// # let _context = rustacuda::quick_init()?;
+    /// let streams = (0..4)
+    ///     .map(|_| Stream::new(StreamFlags::NON_BLOCKING, None))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// // ... enqueue work on the streams ...
+    /// CurrentContext::synchronize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn synchronize() -> CudaResult<()> {
         unsafe {
             cuda::cuCtxSynchronize().to_result()?;
@@ -846,3 +974,114 @@ impl CurrentContext {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_set_then_get_current() {
+        let context = crate::quick_init().unwrap();
+        let unowned = context.get_unowned();
+        CurrentContext::set_current(&unowned).unwrap();
+        assert_eq!(unowned, CurrentContext::get_current().unwrap());
+
+        CurrentContext::set_current(&context).unwrap();
+        assert_eq!(
+            context.get_unowned(),
+            CurrentContext::get_current().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_explicit_drop() {
+        use crate::memory::DeviceBuffer;
+
+        crate::init(crate::CudaFlags::empty()).unwrap();
+        let device = Device::get_device(0).unwrap();
+        let context = Context::create_and_push(ContextFlags::SCHED_AUTO, device).unwrap();
+        let buffer = DeviceBuffer::from_slice(&[1u32, 2, 3]).unwrap();
+        DeviceBuffer::drop(buffer).unwrap();
+        assert!(Context::drop(context).is_ok());
+    }
+
+    #[test]
+    fn test_set_then_get_printf_fifo_size() {
+        let _context = crate::quick_init().unwrap();
+        let size = 2 << 20;
+        CurrentContext::set_resource_limit(ResourceLimit::PrintfFifoSize, size).unwrap();
+        // The driver may round the requested size up.
+        assert!(CurrentContext::get_resource_limit(ResourceLimit::PrintfFifoSize).unwrap() >= size);
+    }
+
+    #[test]
+    fn test_create_with_blocking_sync() {
+        use crate::memory::{CopyDestination, DeviceBuffer};
+
+        crate::init(crate::CudaFlags::empty()).unwrap();
+        let device = Device::get_device(0).unwrap();
+        let _context = Context::create_and_push(ContextFlags::SCHED_BLOCKING_SYNC, device).unwrap();
+        assert!(CurrentContext::get_flags()
+            .unwrap()
+            .contains(ContextFlags::SCHED_BLOCKING_SYNC));
+
+        let buffer = DeviceBuffer::from_slice(&[1u32, 2, 3]).unwrap();
+        let mut host = [0u32; 3];
+        buffer.copy_to(&mut host).unwrap();
+        assert_eq!([1, 2, 3], host);
+    }
+
+    #[test]
+    fn test_enable_then_disable_peer_access() {
+        crate::init(crate::CudaFlags::empty()).unwrap();
+        if Device::num_devices().unwrap() < 2 {
+            return;
+        }
+        let device0 = Device::get_device(0).unwrap();
+        let device1 = Device::get_device(1).unwrap();
+        if !device0.can_access_peer(device1).unwrap() {
+            return;
+        }
+
+        let context0 = Context::create_and_push(ContextFlags::SCHED_AUTO, device0).unwrap();
+        let context1 = Context::create_and_push(ContextFlags::SCHED_AUTO, device1).unwrap();
+        context0.enable_peer_access(&context1).unwrap();
+        assert_eq!(
+            Err(crate::error::CudaError::PeerAccessAlreadyEnabled),
+            context0.enable_peer_access(&context1)
+        );
+        // The calling thread's current context is unaffected.
+        assert_eq!(
+            context1.get_unowned(),
+            CurrentContext::get_current().unwrap()
+        );
+        context0.disable_peer_access(&context1).unwrap();
+        assert_eq!(
+            Err(crate::error::CudaError::PeerAccessNotEnabled),
+            context0.disable_peer_access(&context1)
+        );
+    }
+
+    #[test]
+    fn test_current_api_version() {
+        let context = crate::quick_init().unwrap();
+        assert_eq!(
+            context.get_api_version().unwrap(),
+            CurrentContext::get_api_version().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_then_get_cache_config() {
+        let _context = crate::quick_init().unwrap();
+        for &config in &[
+            CacheConfig::PreferShared,
+            CacheConfig::PreferL1,
+            CacheConfig::PreferEqual,
+            CacheConfig::PreferNone,
+        ] {
+            CurrentContext::set_cache_config(config).unwrap();
+            assert_eq!(config, CurrentContext::get_cache_config().unwrap());
+        }
+    }
+}
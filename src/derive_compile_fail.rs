@@ -1,5 +1,5 @@
 //! This module is a dummy module. It contains doctests that should fail to compile. It's used for
-//! testing the DeriveCopy custom-derive macro and should not contain any actual code.
+//! testing the DeriveCopy and Zeroable custom-derive macros and should not contain any actual code.
 //!
 //! ```compile_fail
 //! #[macro_use]
@@ -64,3 +64,67 @@
 //!
 //! fn main() {}
 //! ```
+//!
+//! ```compile_fail
+//! #[macro_use]
+//! extern crate rustacuda;
+//! extern crate rustacuda_core;
+//!
+//! #[derive(Clone, DeviceCopy)]
+//! struct ShouldFailString {
+//!     id: u32,
+//!     name: String,
+//! }
+//!
+//! fn main() {}
+//! ```
+//!
+//! ```compile_fail
+//! #[macro_use]
+//! extern crate rustacuda;
+//! extern crate rustacuda_core;
+//!
+//! #[derive(Clone, DeviceCopy)]
+//! struct ShouldFailReference<'a> {
+//!     value: &'a u64,
+//! }
+//!
+//! fn main() {}
+//! ```
+//!
+//! ```compile_fail
+//! #[macro_use]
+//! extern crate rustacuda;
+//! extern crate rustacuda_core;
+//! use std::num::NonZeroU64;
+//!
+//! #[derive(Clone, DeviceCopy, Zeroable)]
+//! struct ShouldFailZeroableStruct{v: NonZeroU64}
+//!
+//! fn main() {}
+//! ```
+//!
+//! ```compile_fail
+//! #[macro_use]
+//! extern crate rustacuda;
+//! extern crate rustacuda_core;
+//!
+//! #[derive(Clone, DeviceCopy, Zeroable)]
+//! enum ShouldFailZeroableEnum {
+//!     Unit,
+//!     Tuple(u64),
+//! }
+//!
+//! fn main() {}
+//! ```
+//!
+//! ```compile_fail
+//! extern crate rustacuda;
+//! use rustacuda::memory::DeviceBuffer;
+//! use std::num::NonZeroU64;
+//!
+//! fn main() {
+//!     let _ctx = rustacuda::quick_init().unwrap();
+//!     let _buffer = DeviceBuffer::<NonZeroU64>::zeroed(5).unwrap();
+//! }
+//! ```
@@ -1,9 +1,10 @@
 //! Functions and types for enumerating CUDA devices and retrieving information about them.
 
-use crate::error::{CudaResult, ToResult};
+use crate::error::{CudaError, CudaResult, ToResult};
 use cuda_sys::cuda::*;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::ops::Range;
+use std::os::raw::c_char;
 
 /// All supported device attributes for [Device::get_attribute](struct.Device.html#method.get_attribute)
 #[repr(u32)]
@@ -347,6 +348,174 @@ impl Device {
         }
     }
 
+    /// Returns true if this device supports stream priorities.
+    ///
+    /// On devices without priority support, the priority passed to
+    /// [`Stream::new`](../stream/struct.Stream.html#method.new) is ignored and every stream runs
+    /// at the default priority.
+    ///
+    /// # Example:
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # init(CudaFlags::empty())?;
+    /// use rustacuda::device::Device;
+    /// let device = Device::get_device(0)?;
+    /// if !device.supports_stream_priorities()? {
+    ///     println!("Stream priorities are not supported; all streams use the default priority.");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn supports_stream_priorities(self) -> CudaResult<bool> {
+        self.get_attribute(DeviceAttribute::StreamPrioritiesSupported)
+            .map(|supported| supported != 0)
+    }
+
+    /// Returns the alignment in bytes required of device memory which is bound to a texture.
+    ///
+    /// Pointers to linear memory passed to texture-creation functions must be a multiple of this
+    /// value, or texture creation will fail.
+    ///
+    /// # Example:
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # init(CudaFlags::empty())?;
+    /// use rustacuda::device::Device;
+    /// let device = Device::get_device(0)?;
+    /// println!("Texture alignment: {} bytes", device.texture_alignment()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn texture_alignment(self) -> CudaResult<usize> {
+        self.get_attribute(DeviceAttribute::TextureAlignment)
+            .map(|alignment| alignment as usize)
+    }
+
+    /// Returns the peak clock rate of this device's multiprocessors, in kilohertz.
+    ///
+    /// # Example:
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # init(CudaFlags::empty())?;
+    /// use rustacuda::device::Device;
+    /// let device = Device::get_device(0)?;
+    /// // Two transfers per clock cycle for double data rate memory.
+    /// let bandwidth_bytes_per_sec = 2.0
+    ///     * device.memory_clock_rate()? as f64
+    ///     * 1000.0
+    ///     * (device.memory_bus_width()? / 8) as f64;
+    /// println!("Clock rate: {} kHz", device.clock_rate()?);
+    /// println!("Theoretical bandwidth: {} GB/s", bandwidth_bytes_per_sec / 1e9);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clock_rate(self) -> CudaResult<i32> {
+        self.get_attribute(DeviceAttribute::ClockRate)
+    }
+
+    /// Returns the peak clock rate of this device's memory, in kilohertz.
+    pub fn memory_clock_rate(self) -> CudaResult<i32> {
+        self.get_attribute(DeviceAttribute::MemoryClockRate)
+    }
+
+    /// Returns the width of this device's global memory bus, in bits.
+    pub fn memory_bus_width(self) -> CudaResult<i32> {
+        self.get_attribute(DeviceAttribute::GlobalMemoryBusWidth)
+    }
+
+    /// Returns true if contexts on this device can directly access memory on the `peer` device.
+    /// A device is never reported as a peer of itself.
+    ///
+    /// If so, peer access can be enabled between contexts on the two devices with
+    /// [`Context::enable_peer_access`](../context/struct.Context.html#method.enable_peer_access).
+    ///
+    /// # Example:
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # init(CudaFlags::empty())?;
+    /// use rustacuda::device::Device;
+    /// for device in Device::devices()? {
+    ///     let device = device?;
+    ///     let peer = Device::get_device(0)?;
+    ///     println!("{}: {}", device.name()?, device.can_access_peer(peer)?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn can_access_peer(self, peer: Device) -> CudaResult<bool> {
+        unsafe {
+            let mut can_access = 0i32;
+            cuDeviceCanAccessPeer(&mut can_access as *mut i32, self.device, peer.device)
+                .to_result()?;
+            Ok(can_access != 0)
+        }
+    }
+
+    /// Returns the PCI bus ID of this device, in the form `domain:bus:device.function` with each
+    /// part in hexadecimal, such as `0000:65:00.0`.
+    ///
+    /// This identifies the physical device independently of the ordering of CUDA device ordinals,
+    /// for example to match devices to NUMA nodes or to the IDs reported by other tools.
+    ///
+    /// # Example:
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # init(CudaFlags::empty())?;
+    /// use rustacuda::device::Device;
+    /// let device = Device::get_device(0)?;
+    /// println!("PCI Bus ID: {}", device.pci_bus_id()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pci_bus_id(self) -> CudaResult<String> {
+        unsafe {
+            // The ID is at most 13 characters plus the nul terminator.
+            let mut id = [0 as c_char; 16];
+            cuDeviceGetPCIBusId(id.as_mut_ptr(), id.len() as i32, self.device).to_result()?;
+            let cstr = CStr::from_ptr(id.as_ptr());
+            Ok(cstr.to_string_lossy().into_owned())
+        }
+    }
+
+    /// Returns the device with the given PCI bus ID, as returned by
+    /// [`pci_bus_id`](#method.pci_bus_id).
+    ///
+    /// # Errors:
+    ///
+    /// If `id` contains a nul byte, returns `InvalidValue`. If no device has the given ID, returns
+    /// the error from CUDA.
+    ///
+    /// # Example:
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # init(CudaFlags::empty())?;
+    /// use rustacuda::device::Device;
+    /// let device = Device::get_device(0)?;
+    /// assert_eq!(device, Device::get_by_pci_bus_id(&device.pci_bus_id()?)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_by_pci_bus_id(id: &str) -> CudaResult<Device> {
+        let id = CString::new(id).map_err(|_| CudaError::InvalidValue)?;
+        unsafe {
+            let mut device = Device { device: 0 };
+            cuDeviceGetByPCIBusId(&mut device.device as *mut CUdevice, id.as_ptr()).to_result()?;
+            Ok(device)
+        }
+    }
+
     pub(crate) fn into_inner(self) -> CUdevice {
         self.device
     }
@@ -364,7 +533,12 @@ impl Iterator for Devices {
     fn next(&mut self) -> Option<CudaResult<Device>> {
         self.range.next().map(Device::get_device)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
 }
+impl ExactSizeIterator for Devices {}
 
 #[cfg(test)]
 mod test {
@@ -394,6 +568,16 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_devices_len() -> Result<(), Box<dyn Error>> {
+        test_init()?;
+        let mut devices = Device::devices()?;
+        assert_eq!(Device::num_devices()? as usize, devices.len());
+        let _ = devices.next().unwrap()?;
+        assert_eq!(Device::num_devices()? as usize - 1, devices.len());
+        Ok(())
+    }
+
     #[test]
     fn test_get_name() -> Result<(), Box<dyn Error>> {
         test_init()?;
@@ -410,6 +594,100 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_get_launch_attributes() -> Result<(), Box<dyn Error>> {
+        test_init()?;
+        let device = Device::get_device(0)?;
+        for &attr in &[
+            DeviceAttribute::MultiprocessorCount,
+            DeviceAttribute::MaxThreadsPerMultiprocessor,
+            DeviceAttribute::WarpSize,
+            DeviceAttribute::MaxSharedMemoryPerBlock,
+            DeviceAttribute::ComputeCapabilityMajor,
+            DeviceAttribute::ComputeCapabilityMinor,
+            DeviceAttribute::MemoryClockRate,
+        ] {
+            println!("{:?}: {}", attr, device.get_attribute(attr)?);
+        }
+        assert!(device.get_attribute(DeviceAttribute::MultiprocessorCount)? > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_supports_stream_priorities() -> Result<(), Box<dyn Error>> {
+        let _context = crate::quick_init()?;
+        let supported = Device::get_device(0)?.supports_stream_priorities()?;
+        // Devices without priority support report a range containing only the default priority.
+        let range = crate::context::CurrentContext::get_stream_priority_range()?;
+        assert_eq!(supported, range.least != range.greatest);
+        Ok(())
+    }
+
+    #[test]
+    fn test_texture_alignment() -> Result<(), Box<dyn Error>> {
+        test_init()?;
+        let alignment = Device::get_device(0)?.texture_alignment()?;
+        assert!(alignment > 0);
+        assert!(alignment.is_power_of_two());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clock_rates() -> Result<(), Box<dyn Error>> {
+        test_init()?;
+        let device = Device::get_device(0)?;
+        assert!(device.clock_rate()? > 0);
+        assert!(device.memory_clock_rate()? > 0);
+        assert!(device.memory_bus_width()? > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pci_bus_id_round_trip() -> Result<(), Box<dyn Error>> {
+        test_init()?;
+        let device = Device::get_device(0)?;
+        let id = device.pci_bus_id()?;
+        assert!(!id.is_empty());
+        assert_eq!(device, Device::get_by_pci_bus_id(&id)?);
+        assert_eq!(
+            Err(CudaError::InvalidValue),
+            Device::get_by_pci_bus_id("0000:00\0:00.0")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_can_access_peer() -> Result<(), Box<dyn Error>> {
+        use crate::context::{Context, ContextFlags};
+
+        test_init()?;
+        let devices = Device::devices()?.collect::<CudaResult<Vec<_>>>()?;
+        for &device in &devices {
+            assert!(!device.can_access_peer(device)?);
+            let context = Context::create_and_push(ContextFlags::SCHED_AUTO, device)?;
+            assert!(context.enable_peer_access(&context).is_err());
+
+            // Enabling peer access must succeed exactly when the devices report that they can.
+            for &peer in devices.iter().filter(|&&peer| peer != device) {
+                let can_access = device.can_access_peer(peer)?;
+                let peer_context = Context::create_and_push(ContextFlags::SCHED_AUTO, peer)?;
+                let result = context.enable_peer_access(&peer_context);
+                assert_eq!(
+                    can_access,
+                    result.is_ok(),
+                    "{:?} -> {:?}: {:?}",
+                    device,
+                    peer,
+                    result
+                );
+                if can_access {
+                    context.disable_peer_access(&peer_context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     // Ensure that the two enums always stay aligned.
     #[test]
     fn test_enums_align() {
@@ -6,6 +6,9 @@
 //! the CUDA API. It is important to note that nearly every function in CUDA (and therefore
 //! RustaCUDA) can fail. Even those functions which have no normal failure conditions can return
 //! errors related to previous asynchronous launches.
+//!
+//! When a bare `CudaError` isn't descriptive enough, the [`ResultExt`](trait.ResultExt.html)
+//! trait can be used to attach a label describing the operation which failed.
 
 use cuda_sys::cuda::{self, cudaError_t};
 use std::error::Error;
@@ -87,6 +90,38 @@ pub enum CudaError {
     #[doc(hidden)]
     __Nonexhaustive,
 }
+impl CudaError {
+    /// Returns the driver's name for this error, such as `CUDA_ERROR_ILLEGAL_ADDRESS`.
+    ///
+    /// The human-readable description of the error is available through the `Display`
+    /// implementation. Neither requires the driver to be initialized or a context to exist.
+    ///
+    /// Returns `None` for errors which are generated by RustaCUDA rather than the driver.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use rustacuda::error::CudaError;
+    /// assert_eq!(Some("CUDA_ERROR_INVALID_VALUE"), CudaError::InvalidValue.name());
+    /// println!("{}", CudaError::InvalidValue); // "invalid argument"
+    /// ```
+    pub fn name(self) -> Option<&'static str> {
+        let value = self as u32;
+        if value > 999 {
+            return None;
+        }
+        let mut ptr: *const c_char = ptr::null();
+        unsafe {
+            // Every CudaError below 1000 has the same value as a variant of `cudaError_t`.
+            let code = mem::transmute::<u32, cudaError_t>(value);
+            cuda::cuGetErrorName(code, &mut ptr as *mut *const c_char)
+                .to_result()
+                .ok()?;
+            // The driver's error names are static strings.
+            CStr::from_ptr(ptr).to_str().ok()
+        }
+    }
+}
 impl fmt::Display for CudaError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -96,11 +131,12 @@ impl fmt::Display for CudaError {
                 let value = other as u32;
                 let mut ptr: *const c_char = ptr::null();
                 unsafe {
-                    cuda::cuGetErrorString(mem::transmute(value), &mut ptr as *mut *const c_char)
+                    let code = mem::transmute::<u32, cudaError_t>(value);
+                    cuda::cuGetErrorString(code, &mut ptr as *mut *const c_char)
                         .to_result()
                         .map_err(|_| fmt::Error)?;
                     let cstr = CStr::from_ptr(ptr);
-                    write!(f, "{:?}", cstr)
+                    write!(f, "{}", cstr.to_string_lossy())
                 }
             }
             // This shouldn't happen
@@ -116,6 +152,69 @@ pub type CudaResult<T> = Result<T, CudaError>;
 /// Special result type for `drop` functions which includes the un-dropped value with the error.
 pub type DropResult<T> = Result<(), (CudaError, T)>;
 
+/// A `CudaError` labeled with a description of the operation which failed.
+///
+/// This is created by the [`ResultExt::context`](trait.ResultExt.html#tymethod.context) method.
+/// The `Display` output is only the label. The underlying `CudaError` is returned by
+/// `Error::source`, so error reporters which print the chain of sources, such as `anyhow`'s
+/// `{:#}` format, print the label followed by the driver's description of the error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContextError {
+    context: String,
+    error: CudaError,
+}
+impl ContextError {
+    /// Returns the label describing the operation which failed.
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+
+    /// Returns the underlying CUDA error.
+    pub fn error(&self) -> CudaError {
+        self.error
+    }
+}
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.context)
+    }
+}
+impl Error for ContextError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Extension trait for attaching a description of the failed operation to a `CudaResult`.
+///
+/// # Examples:
+///
+/// ```
+/// # use rustacuda::*;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let _ctx = quick_init()?;
+/// use rustacuda::error::ResultExt;
+/// use rustacuda::memory::DeviceBuffer;
+///
+/// let weights = [0.5f32; 16];
+/// let buffer = DeviceBuffer::from_slice(&weights).context("uploading weights")?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ResultExt<T> {
+    /// Label the error, if any, with a description of the operation which produced it.
+    fn context(self, context: &str) -> Result<T, ContextError>;
+}
+impl<T> ResultExt<T> for CudaResult<T> {
+    fn context(self, context: &str) -> Result<T, ContextError> {
+        self.map_err(|error| ContextError {
+            context: context.to_owned(),
+            error,
+        })
+    }
+}
+
 pub(crate) trait ToResult {
     fn to_result(self) -> CudaResult<()>;
 }
@@ -208,3 +307,98 @@ impl ToResult for cudaError_t {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_error_messages_without_context() {
+        let message = CudaError::IllegalAddress.to_string();
+        assert!(!message.is_empty());
+        assert!(!message.contains('"'));
+        assert_eq!(
+            Some("CUDA_ERROR_ILLEGAL_ADDRESS"),
+            CudaError::IllegalAddress.name()
+        );
+        assert_eq!(None, CudaError::InvalidMemoryAllocation.name());
+    }
+
+    #[test]
+    fn test_boxed_error_round_trip() {
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(CudaError::LaunchFailed);
+        assert!(!boxed.to_string().is_empty());
+        assert!(boxed.source().is_none());
+        assert_eq!(
+            Some(&CudaError::LaunchFailed),
+            boxed.downcast_ref::<CudaError>()
+        );
+
+        let result: CudaResult<()> = Err(CudaError::LaunchFailed);
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(result.context("launch").unwrap_err());
+        let source = boxed.source().unwrap();
+        assert_eq!(
+            Some(&CudaError::LaunchFailed),
+            source.downcast_ref::<CudaError>()
+        );
+    }
+
+    #[test]
+    fn test_launch_error_conversion() {
+        let table = [
+            (
+                cudaError_t::CUDA_ERROR_LAUNCH_OUT_OF_RESOURCES,
+                CudaError::LaunchOutOfResources,
+            ),
+            (
+                cudaError_t::CUDA_ERROR_LAUNCH_TIMEOUT,
+                CudaError::LaunchTimeout,
+            ),
+            (
+                cudaError_t::CUDA_ERROR_LAUNCH_INCOMPATIBLE_TEXTURING,
+                CudaError::LaunchIncompatibleTexturing,
+            ),
+            (
+                cudaError_t::CUDA_ERROR_LAUNCH_FAILED,
+                CudaError::LaunchFailed,
+            ),
+        ];
+        for &(code, error) in &table {
+            assert_eq!(Err(error), code.to_result());
+            assert_eq!(code as u32, error as u32);
+        }
+    }
+
+    #[test]
+    fn test_context_message() {
+        let result: CudaResult<()> = Err(CudaError::InvalidValue);
+        let error = result.context("uploading weights").unwrap_err();
+        assert_eq!("uploading weights", error.context());
+        assert_eq!(CudaError::InvalidValue, error.error());
+
+        assert_eq!("uploading weights", error.to_string());
+        let source = error.source().unwrap().downcast_ref::<CudaError>();
+        assert_eq!(Some(&CudaError::InvalidValue), source);
+    }
+
+    #[test]
+    fn test_context_ok() {
+        let result: CudaResult<u32> = Ok(5);
+        assert_eq!(Ok(5), result.context("unused"));
+    }
+
+    #[test]
+    fn test_boxed_error_message() {
+        fn upload() -> Result<(), Box<dyn Error>> {
+            let result: CudaResult<()> = Err(CudaError::OutOfMemory);
+            result.context("allocating scratch space")?;
+            Ok(())
+        }
+        let error = upload().unwrap_err();
+        assert_eq!("allocating scratch space", error.to_string());
+        assert_eq!(
+            CudaError::OutOfMemory.to_string(),
+            error.source().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Events for synchronizing work between streams.
+//!
+//! An event is a marker which can be recorded into a stream. The event is considered complete when
+//! all work queued in the stream before the event was recorded has been completed by the device.
+//! Other streams can be made to wait until an event has completed, which allows work queued in
+//! different streams to be sequenced without blocking the host.
+
+use crate::error::{CudaError, CudaResult, DropResult, ToResult};
+use crate::stream::Stream;
+use cuda_sys::cuda::{self, CUevent};
+use std::mem;
+use std::ptr;
+
+bitflags! {
+    /// Bit flags for configuring a CUDA Event.
+    pub struct EventFlags: u32 {
+        /// No flags set.
+        const DEFAULT = 0x00;
+
+        /// Specifies that the host thread waiting on this event should block until the event is
+        /// complete, rather than spinning.
+        const BLOCKING_SYNC = 0x01;
+
+        /// Specifies that this event does not record timing data. Events used only for
+        /// synchronization should use this flag, as it makes them cheaper to record and wait on.
+        const DISABLE_TIMING = 0x02;
+
+        /// Specifies that this event may be used as an interprocess event. This flag must be
+        /// combined with `DISABLE_TIMING`.
+        const INTERPROCESS = 0x04;
+    }
+}
+
+/// A marker which can be recorded into a stream and waited on.
+///
+/// See the module-level documentation for more information.
+#[derive(Debug)]
+pub struct Event {
+    inner: CUevent,
+}
+impl Event {
+    /// Create a new event with the given flags.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::event::{Event, EventFlags};
+    ///
+    /// let event = Event::new(EventFlags::DISABLE_TIMING)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(flags: EventFlags) -> CudaResult<Self> {
+        unsafe {
+            let mut event = Event {
+                inner: ptr::null_mut(),
+            };
+            cuda::cuEventCreate(&mut event.inner as *mut CUevent, flags.bits()).to_result()?;
+            Ok(event)
+        }
+    }
+
+    /// Record this event into the given stream.
+    ///
+    /// The event will be completed once all work currently queued in `stream` has been completed.
+    /// If the event has been recorded before, this replaces the previously recorded state.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::event::{Event, EventFlags};
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    ///
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let event = Event::new(EventFlags::DISABLE_TIMING)?;
+    ///
+    /// // ... queue up some work on the stream
+    ///
+    /// event.record(&stream)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn record(&self, stream: &Stream) -> CudaResult<()> {
+        unsafe { cuda::cuEventRecord(self.inner, stream.as_inner()).to_result() }
+    }
+
+    /// Wait until this event has completed.
+    ///
+    /// If the event has not been recorded, this returns immediately. If the event was created with
+    /// `EventFlags::BLOCKING_SYNC`, the calling thread blocks until the event completes; otherwise
+    /// it spins.
+    ///
+    /// # Errors:
+    ///
+    /// If the event or any of the work before it failed, returns the error from CUDA.
+    pub fn synchronize(&self) -> CudaResult<()> {
+        unsafe { cuda::cuEventSynchronize(self.inner).to_result() }
+    }
+
+    /// Check whether this event has completed, without blocking.
+    ///
+    /// Returns `Ok(true)` if all work recorded before the event has completed (or the event has not
+    /// been recorded), and `Ok(false)` if work is still pending.
+    ///
+    /// # Errors:
+    ///
+    /// If the event or any of the work before it failed, returns the error from CUDA.
+    pub fn query(&self) -> CudaResult<bool> {
+        unsafe {
+            match cuda::cuEventQuery(self.inner).to_result() {
+                Ok(()) => Ok(true),
+                Err(CudaError::NotReady) => Ok(false),
+                Err(e) => Err(e),
+            }
+        }
+    }
+
+    /// Return the time elapsed in milliseconds between `earlier` and this event.
+    ///
+    /// Both events must have been recorded and completed, and neither may have been created with
+    /// `EventFlags::DISABLE_TIMING`. The resolution is around half a microsecond.
+    ///
+    /// # Errors:
+    ///
+    /// If either event has not completed, returns `CudaError::NotReady`. If either event was not
+    /// recorded or was created with `DISABLE_TIMING`, returns `CudaError::InvalidHandle`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::event::{Event, EventFlags};
+    /// use rustacuda::memory::{AsyncCopyDestination, DeviceBuffer, LockedBuffer};
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    ///
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let start = Event::new(EventFlags::DEFAULT)?;
+    /// let stop = Event::new(EventFlags::DEFAULT)?;
+    ///
+    /// let host = LockedBuffer::new(&0u8, 1 << 20)?;
+    /// let mut device = unsafe { DeviceBuffer::uninitialized(1 << 20)? };
+    ///
+    /// start.record(&stream)?;
+    /// unsafe { device.async_copy_from(&host, &stream)? };
+    /// stop.record(&stream)?;
+    /// stop.synchronize()?;
+    ///
+    /// let millis = stop.elapsed_time_f32(&start)?;
+    /// println!("Copied 1MiB in {}ms", millis);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn elapsed_time_f32(&self, earlier: &Event) -> CudaResult<f32> {
+        unsafe {
+            let mut millis = 0.0f32;
+            cuda::cuEventElapsedTime(&mut millis as *mut f32, earlier.inner, self.inner)
+                .to_result()?;
+            Ok(millis)
+        }
+    }
+
+    pub(crate) fn as_inner(&self) -> CUevent {
+        self.inner
+    }
+
+    /// Destroy an `Event`, returning an error.
+    ///
+    /// Destroying an event can return errors from previous asynchronous work. This function
+    /// destroys the given event and returns the error and the un-destroyed event on failure.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::event::{Event, EventFlags};
+    ///
+    /// let event = Event::new(EventFlags::DEFAULT)?;
+    /// match Event::drop(event) {
+    ///     Ok(()) => println!("Successfully destroyed"),
+    ///     Err((e, event)) => {
+    ///         println!("Failed to destroy event: {:?}", e);
+    ///         // Do something with event
+    ///     },
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn drop(mut event: Event) -> DropResult<Event> {
+        if event.inner.is_null() {
+            return Ok(());
+        }
+
+        unsafe {
+            let inner = mem::replace(&mut event.inner, ptr::null_mut());
+            match cuda::cuEventDestroy_v2(inner).to_result() {
+                Ok(()) => {
+                    mem::forget(event);
+                    Ok(())
+                }
+                Err(e) => Err((e, Event { inner })),
+            }
+        }
+    }
+}
+impl Drop for Event {
+    fn drop(&mut self) {
+        if self.inner.is_null() {
+            return;
+        }
+
+        unsafe {
+            let inner = mem::replace(&mut self.inner, ptr::null_mut());
+            // No choice but to panic here.
+            cuda::cuEventDestroy_v2(inner)
+                .to_result()
+                .expect("Failed to destroy CUDA event.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stream::StreamFlags;
+
+    #[test]
+    fn test_create_and_record() {
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let event = Event::new(EventFlags::DISABLE_TIMING).unwrap();
+        event.record(&stream).unwrap();
+        stream.synchronize().unwrap();
+        Event::drop(event).unwrap();
+    }
+
+    #[test]
+    fn test_synchronize_and_query() {
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let event = Event::new(EventFlags::DISABLE_TIMING).unwrap();
+        event.record(&stream).unwrap();
+        event.synchronize().unwrap();
+        assert!(event.query().unwrap());
+    }
+
+    #[test]
+    fn test_elapsed_time() {
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let start = Event::new(EventFlags::DEFAULT).unwrap();
+        let stop = Event::new(EventFlags::DEFAULT).unwrap();
+        start.record(&stream).unwrap();
+        stop.record(&stream).unwrap();
+        stop.synchronize().unwrap();
+        assert!(stop.elapsed_time_f32(&start).unwrap() >= 0.0);
+    }
+
+    #[test]
+    fn test_elapsed_time_disable_timing() {
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let start = Event::new(EventFlags::DISABLE_TIMING).unwrap();
+        let stop = Event::new(EventFlags::DISABLE_TIMING).unwrap();
+        start.record(&stream).unwrap();
+        stop.record(&stream).unwrap();
+        stop.synchronize().unwrap();
+        assert_eq!(Err(CudaError::InvalidHandle), stop.elapsed_time_f32(&start));
+    }
+}
@@ -1,9 +1,13 @@
 //! Functions and types for working with CUDA kernels.
 
-use crate::context::{CacheConfig, SharedMemoryConfig};
-use crate::error::{CudaResult, ToResult};
+use crate::context::{CacheConfig, CurrentContext, SharedMemoryConfig};
+use crate::device::{Device, DeviceAttribute};
+use crate::error::{ContextError, CudaError, CudaResult, ResultExt, ToResult};
+use crate::memory::DeviceCopy;
 use crate::module::Module;
+use crate::stream::{Stream, StreamCompletion};
 use cuda_sys::cuda::{self, CUfunction};
+use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::mem::transmute;
 
@@ -117,6 +121,150 @@ impl<'a> From<&'a BlockSize> for BlockSize {
     }
 }
 
+/// Grid and block dimensions for a kernel launch, as suggested by
+/// [Function::suggest_launch](struct.Function.html#method.suggest_launch).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchConfig {
+    /// Number of thread blocks in the grid
+    pub grid: GridSize,
+    /// Number of threads in each block
+    pub block: BlockSize,
+}
+
+impl LaunchConfig {
+    /// Check that a launch with this configuration and `shared_mem_bytes` bytes of dynamic shared
+    /// memory per block is within the limits of `device`.
+    ///
+    /// This checks each grid and block dimension against the device's maximum, the total number
+    /// of threads per block and the amount of shared memory per block. A kernel may have lower
+    /// limits than the device, for example if it uses many registers; use
+    /// [`launch_checked!`](../macro.launch_checked.html) to check those as well.
+    ///
+    /// Since each check queries the device, only `launch_checked!` validates its launches
+    /// automatically. `launch!`, [`TypedFunction::launch`](struct.TypedFunction.html#method.launch),
+    /// [`Function::launch_async`](struct.Function.html#method.launch_async) and
+    /// [`Stream::launch_batch`](../stream/struct.Stream.html#method.launch_batch) don't, so call
+    /// this first when a configuration might exceed the device's limits.
+    ///
+    /// # Errors:
+    ///
+    /// If a dimension is zero or a limit is exceeded, returns `InvalidValue` labeled with the limit
+    /// which was violated. If querying the device's limits fails, returns the error from CUDA.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::context::CurrentContext;
+    /// use rustacuda::function::{BlockSize, GridSize, LaunchConfig};
+    /// let device = CurrentContext::get_device()?;
+    /// let config = LaunchConfig {
+    ///     grid: GridSize::x(1),
+    ///     block: BlockSize::xyz(32, 32, 32),
+    /// };
+    /// let error = config.validate(device, 0).unwrap_err();
+    /// println!("{}", error);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self, device: Device, shared_mem_bytes: u32) -> Result<(), ContextError> {
+        let (grid, block) = (&self.grid, &self.block);
+        let limit = |attr| {
+            device
+                .get_attribute(attr)
+                .map(|value| value as u32)
+                .context("querying the device's launch limits")
+        };
+        let dims = [
+            ("block x", block.x, limit(DeviceAttribute::MaxBlockDimX)?),
+            ("block y", block.y, limit(DeviceAttribute::MaxBlockDimY)?),
+            ("block z", block.z, limit(DeviceAttribute::MaxBlockDimZ)?),
+            ("grid x", grid.x, limit(DeviceAttribute::MaxGridDimX)?),
+            ("grid y", grid.y, limit(DeviceAttribute::MaxGridDimY)?),
+            ("grid z", grid.z, limit(DeviceAttribute::MaxGridDimZ)?),
+        ];
+        for &(name, value, max) in &dims {
+            if value == 0 || value > max {
+                return Err(CudaError::InvalidValue).context(&format!(
+                    "{} dimension {} is outside the device's range of 1 to {}",
+                    name, value, max
+                ));
+            }
+        }
+
+        let threads = u64::from(block.x) * u64::from(block.y) * u64::from(block.z);
+        let max_threads = u64::from(limit(DeviceAttribute::MaxThreadsPerBlock)?);
+        if threads > max_threads {
+            return Err(CudaError::InvalidValue).context(&format!(
+                "block of {} threads exceeds the device's limit of {} threads per block",
+                threads, max_threads
+            ));
+        }
+
+        let max_shared = limit(DeviceAttribute::MaxSharedMemoryPerBlock)?;
+        if shared_mem_bytes > max_shared {
+            return Err(CudaError::InvalidValue).context(&format!(
+                "{} bytes of shared memory exceeds the device's limit of {} bytes per block",
+                shared_mem_bytes, max_shared
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A single argument to a kernel launched with
+/// [Stream::launch_batch](../stream/struct.Stream.html#method.launch_batch).
+///
+/// This is implemented for every [`DeviceCopy`](../memory/trait.DeviceCopy.html) type, so that
+/// arguments of different types can be passed together as a slice of `&dyn KernelArg`.
+pub trait KernelArg {
+    /// Returns a pointer to the argument's value, in the form expected by `cuLaunchKernel`.
+    fn as_kernel_param(&self) -> *mut c_void;
+}
+impl<T: DeviceCopy> KernelArg for T {
+    fn as_kernel_param(&self) -> *mut c_void {
+        self as *const T as *mut c_void
+    }
+}
+
+/// A tuple of kernel arguments, for launching a [`TypedFunction`](struct.TypedFunction.html).
+///
+/// This is implemented for tuples of up to twelve [`DeviceCopy`](../memory/trait.DeviceCopy.html)
+/// values.
+pub trait KernelParams {
+    /// Returns pointers to each argument's value, in the form expected by `cuLaunchKernel`.
+    fn kernel_params(&self) -> Vec<*mut c_void>;
+}
+
+macro_rules! impl_kernel_params {
+    ($($name:ident)*) => {
+        impl<$($name: DeviceCopy),*> KernelParams for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn kernel_params(&self) -> Vec<*mut c_void> {
+                let ($(ref $name,)*) = *self;
+                vec![$($name.as_kernel_param()),*]
+            }
+        }
+    }
+}
+
+impl_kernel_params!();
+impl_kernel_params!(A);
+impl_kernel_params!(A B);
+impl_kernel_params!(A B C);
+impl_kernel_params!(A B C D);
+impl_kernel_params!(A B C D E);
+impl_kernel_params!(A B C D E F);
+impl_kernel_params!(A B C D E F G);
+impl_kernel_params!(A B C D E F G H);
+impl_kernel_params!(A B C D E F G H I);
+impl_kernel_params!(A B C D E F G H I J);
+impl_kernel_params!(A B C D E F G H I J K);
+impl_kernel_params!(A B C D E F G H I J K L);
+
 /// All supported function attributes for [Function::get_attribute](struct.Function.html#method.get_attribute)
 #[repr(u32)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -263,11 +411,324 @@ impl<'a> Function<'a> {
         unsafe { cuda::cuFuncSetSharedMemConfig(self.inner, transmute(cfg)).to_result() }
     }
 
+    /// Returns the maximum number of blocks of this function which can be resident on a single
+    /// multiprocessor at once, given the block size and the bytes of dynamic shared memory per
+    /// block.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// # use rustacuda::module::Module;
+    /// # use std::ffi::CString;
+    /// # let ptx = CString::new(include_str!("../resources/add.ptx"))?;
+    /// # let module = Module::load_from_string(&ptx)?;
+    /// # let name = CString::new("sum")?;
+    /// let function = module.get_function(&name)?;
+    /// let blocks = function.max_active_blocks_per_multiprocessor(256, 0)?;
+    /// println!("Up to {} blocks of 256 threads fit on each multiprocessor", blocks);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_active_blocks_per_multiprocessor(
+        &self,
+        block_size: u32,
+        dynamic_smem: usize,
+    ) -> CudaResult<u32> {
+        unsafe {
+            let mut num_blocks = 0i32;
+            cuda::cuOccupancyMaxActiveBlocksPerMultiprocessor(
+                &mut num_blocks as *mut i32,
+                self.inner,
+                block_size as i32,
+                dynamic_smem,
+            )
+            .to_result()?;
+            Ok(num_blocks as u32)
+        }
+    }
+
+    /// Returns a launch configuration which achieves the maximum occupancy for this function,
+    /// given the bytes of dynamic shared memory per block.
+    ///
+    /// The result is a tuple of `(min_grid_size, block_size)`, where `block_size` is the number of
+    /// threads per block with the highest occupancy and `min_grid_size` is the smallest number of
+    /// blocks needed to reach that occupancy across the whole device.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// # use rustacuda::module::Module;
+    /// # use std::ffi::CString;
+    /// # let ptx = CString::new(include_str!("../resources/add.ptx"))?;
+    /// # let module = Module::load_from_string(&ptx)?;
+    /// # let name = CString::new("sum")?;
+    /// let function = module.get_function(&name)?;
+    /// let (min_grid_size, block_size) = function.suggested_launch_configuration(0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn suggested_launch_configuration(&self, dynamic_smem: usize) -> CudaResult<(u32, u32)> {
+        unsafe {
+            let mut min_grid_size = 0i32;
+            let mut block_size = 0i32;
+            cuda::cuOccupancyMaxPotentialBlockSize(
+                &mut min_grid_size as *mut i32,
+                &mut block_size as *mut i32,
+                self.inner,
+                None,
+                dynamic_smem,
+                0,
+            )
+            .to_result()?;
+            Ok((min_grid_size as u32, block_size as u32))
+        }
+    }
+
+    /// Suggests a one-dimensional launch configuration for processing `total_elems` elements, one
+    /// per thread.
+    ///
+    /// The block size is the one which achieves the maximum occupancy for this function (see
+    /// [`suggested_launch_configuration`](#method.suggested_launch_configuration)), and the grid
+    /// is just large enough to cover all of the elements. The kernel should check its thread index
+    /// against the number of elements, since the last block may be only partly used.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// # use rustacuda::module::Module;
+    /// # use std::ffi::CString;
+    /// # let ptx = CString::new(include_str!("../resources/add.ptx"))?;
+    /// # let module = Module::load_from_string(&ptx)?;
+    /// # let name = CString::new("sum")?;
+    /// let function = module.get_function(&name)?;
+    /// let config = function.suggest_launch(1_000_000)?;
+    /// assert!(config.grid.x * config.block.x >= 1_000_000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn suggest_launch(&self, total_elems: u32) -> CudaResult<LaunchConfig> {
+        let (_, block_size) = self.suggested_launch_configuration(0)?;
+        let grid_size = if total_elems == 0 {
+            1
+        } else {
+            (total_elems - 1) / block_size + 1
+        };
+        Ok(LaunchConfig {
+            grid: GridSize::x(grid_size),
+            block: BlockSize::x(block_size),
+        })
+    }
+
+    /// Launch this function on `stream` and return a future which resolves when it completes.
+    ///
+    /// The launch uses the grid and block sizes of `config` and `shared_mem_bytes` bytes of
+    /// dynamic shared memory per block, as in [`launch!`](../macro.launch.html). The returned
+    /// future resolves once all work queued on `stream` up to and including this launch has
+    /// completed; see [`Stream::completion`](../stream/struct.Stream.html#method.completion).
+    ///
+    /// `config` is not checked against the device's limits before launching; use
+    /// [`LaunchConfig::validate`](struct.LaunchConfig.html#method.validate) for that.
+    ///
+    /// # Errors:
+    ///
+    /// If the launch fails, returns the error from CUDA. The future resolves to the error from any
+    /// failure while the kernel runs.
+    ///
+    /// # Safety
+    ///
+    /// The argument types must match the parameters of the kernel, and any memory the kernel
+    /// accesses must remain valid until the future resolves.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// # use rustacuda::module::Module;
+    /// # use rustacuda::memory::DeviceBuffer;
+    /// # use rustacuda::stream::{Stream, StreamFlags};
+    /// # use std::ffi::CString;
+    /// # let ptx = CString::new(include_str!("../resources/add.ptx"))?;
+    /// # let module = Module::load_from_string(&ptx)?;
+    /// # let name = CString::new("sum")?;
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let function = module.get_function(&name)?;
+    /// let mut x = DeviceBuffer::from_slice(&[1.0f32; 64])?;
+    /// let mut y = DeviceBuffer::from_slice(&[2.0f32; 64])?;
+    /// let mut out = DeviceBuffer::from_slice(&[0.0f32; 64])?;
+    /// let config = function.suggest_launch(64)?;
+    /// let done = unsafe {
+    ///     // `sum` takes an `int` count, so pass an `i32` rather than `out.len()`'s `usize`.
+    ///     let count = out.len() as i32;
+    ///     let args = (x.as_device_ptr(), y.as_device_ptr(), out.as_device_ptr(), count);
+    ///     function.launch_async(&stream, &config, 0, args)?
+    /// };
+    /// // ... later, in asynchronous code:
+    /// // done.await?;
+    /// # stream.synchronize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn launch_async<Args: KernelParams>(
+        &self,
+        stream: &Stream,
+        config: &LaunchConfig,
+        shared_mem_bytes: u32,
+        args: Args,
+    ) -> CudaResult<StreamCompletion> {
+        let params = args.kernel_params();
+        stream.launch(
+            self,
+            config.grid.clone(),
+            config.block.clone(),
+            shared_mem_bytes,
+            &params,
+        )?;
+        stream.completion()
+    }
+
+    // Check a launch against the limits of this function and the current device, for
+    // `launch_checked!`. The error is labeled with the limit which was exceeded.
+    pub(crate) fn check_launch_dims(
+        &self,
+        grid: &GridSize,
+        block: &BlockSize,
+        shared_mem_bytes: u32,
+    ) -> Result<(), ContextError> {
+        let device = CurrentContext::get_device().context("querying the current device")?;
+        let config = LaunchConfig {
+            grid: grid.clone(),
+            block: block.clone(),
+        };
+        config.validate(device, shared_mem_bytes)?;
+
+        let threads = u64::from(block.x) * u64::from(block.y) * u64::from(block.z);
+        let max_threads = self
+            .get_attribute(FunctionAttribute::MaxThreadsPerBlock)
+            .context("querying the function's thread limit")? as u64;
+        if threads > max_threads {
+            return Err(CudaError::InvalidValue).context(&format!(
+                "block of {} threads exceeds the function's limit of {} threads per block",
+                threads, max_threads
+            ));
+        }
+        Ok(())
+    }
+
     pub(crate) fn to_inner(&self) -> CUfunction {
         self.inner
     }
 }
 
+/// A kernel function together with the types of its arguments.
+///
+/// Launching a `TypedFunction` requires a tuple of exactly the declared argument types, so
+/// forgetting an argument or passing one of the wrong type is a compile error rather than garbage
+/// results. The argument types are not checked against the kernel itself, so they must be
+/// declared correctly when the `TypedFunction` is created.
+///
+/// # Examples:
+///
+/// ```
+/// # use rustacuda::*;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let _ctx = quick_init()?;
+/// use rustacuda::function::TypedFunction;
+/// use rustacuda::memory::*;
+/// use rustacuda::module::Module;
+/// use rustacuda::stream::*;
+/// use std::ffi::CString;
+///
+/// let ptx = CString::new(include_str!("../resources/add.ptx"))?;
+/// let module = Module::load_from_string(&ptx)?;
+/// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+/// let sum: TypedFunction<(DevicePointer<f32>, DevicePointer<f32>, DevicePointer<f32>, i32)> =
+///     TypedFunction::new(module.get_function(&CString::new("sum")?)?);
+///
+/// let mut x = DeviceBuffer::from_slice(&[1.0f32; 10])?;
+/// let mut y = DeviceBuffer::from_slice(&[2.0f32; 10])?;
+/// let mut out = DeviceBuffer::from_slice(&[0.0f32; 10])?;
+/// unsafe {
+///     sum.launch(&stream, 1, 10, 0, (
+///         x.as_device_ptr(),
+///         y.as_device_ptr(),
+///         out.as_device_ptr(),
+///         10,
+///     ))?;
+/// }
+/// stream.synchronize()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TypedFunction<'a, Args> {
+    function: Function<'a>,
+    args: PhantomData<fn(Args)>,
+}
+impl<'a, Args: KernelParams> TypedFunction<'a, Args> {
+    /// Declare the argument types of `function`.
+    pub fn new(function: Function<'a>) -> Self {
+        TypedFunction {
+            function,
+            args: PhantomData,
+        }
+    }
+
+    /// Returns the underlying untyped function.
+    pub fn function(&self) -> &Function<'a> {
+        &self.function
+    }
+
+    /// Launch the kernel asynchronously on `stream` with the given arguments.
+    ///
+    /// The grid size, block size and shared memory size behave as they do in
+    /// [`launch!`](../macro.launch.html), and like it this doesn't check them against the device's
+    /// limits. A launch which exceeds them fails with the driver's error; use
+    /// [`LaunchConfig::validate`](struct.LaunchConfig.html#method.validate) or
+    /// [`launch_checked!`](../macro.launch_checked.html) for a more descriptive one.
+    ///
+    /// # Safety
+    ///
+    /// The declared argument types must match the parameters of the kernel, and any memory the
+    /// kernel accesses must remain valid until it completes.
+    pub unsafe fn launch<G, B>(
+        &self,
+        stream: &Stream,
+        grid_size: G,
+        block_size: B,
+        shared_mem_bytes: u32,
+        args: Args,
+    ) -> CudaResult<()>
+    where
+        G: Into<GridSize>,
+        B: Into<BlockSize>,
+    {
+        let params = args.kernel_params();
+        stream.launch(
+            &self.function,
+            grid_size,
+            block_size,
+            shared_mem_bytes,
+            &params,
+        )
+    }
+}
+
 /// Launch a kernel function asynchronously.
 ///
 /// # Syntax:
@@ -281,9 +742,10 @@ impl<'a> Function<'a> {
 ///
 /// This will load a kernel called `function_name` from the module `module` and launch it with
 /// the given grid/block size on the given stream. Unlike in CUDA C, the shared memory size and
-/// stream parameters are not optional. The shared memory size is a number of bytes per thread for
-/// dynamic shared memory (Note that this uses `extern __shared__ int x[]` in CUDA C, not the
-/// fixed-length arrays created by `__shared__ int x[64]`. This will usually be zero.).
+/// stream parameters are not optional. The shared memory size is the number of bytes of dynamic
+/// shared memory to allocate for each thread block, and is passed through to `cuLaunchKernel`
+/// unchanged (Note that this sizes `extern __shared__ int x[]` in CUDA C, not the fixed-length
+/// arrays created by `__shared__ int x[64]`. This will usually be zero.).
 /// `stream` must be the name of a [`Stream`](stream/struct.Stream.html) value.
 /// `grid` can be any value which implements [`Into<GridSize>`](function/struct.GridSize.html) (such as
 /// `u32` values, tuples of up to three `u32` values, and GridSize structures) and likewise `block`
@@ -411,13 +873,93 @@ macro_rules! launch {
     };
 }
 
+/// Launch a kernel function asynchronously, after checking the launch dimensions.
+///
+/// This has the same syntax as [`launch!`](macro.launch.html), but before launching it checks the
+/// grid and block dimensions against the limits of the current device, and the total number of
+/// threads per block against the limit for the function. If a limit is exceeded, the kernel is
+/// not launched and a [`ContextError`](error/struct.ContextError.html) is returned, wrapping
+/// `CudaError::InvalidValue` and describing which limit was exceeded. Errors from the launch
+/// itself are also returned as a `ContextError`.
+///
+/// Checking the limits costs several driver calls per launch, so `launch!` should be used instead
+/// in hot loops once the launch configuration is known to be valid.
+///
+/// # Examples:
+///
+/// ```
+/// # #[macro_use]
+/// # use rustacuda::*;
+/// # use std::error::Error;
+/// use rustacuda::memory::*;
+/// use rustacuda::module::Module;
+/// use rustacuda::stream::*;
+/// use std::ffi::CString;
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let _ctx = rustacuda::quick_init()?;
+/// let ptx = CString::new(include_str!("../resources/add.ptx"))?;
+/// let module = Module::load_from_string(&ptx)?;
+/// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+///
+/// let mut in_x = DeviceBuffer::from_slice(&[1.0f32; 10])?;
+/// let mut in_y = DeviceBuffer::from_slice(&[2.0f32; 10])?;
+/// let mut out = DeviceBuffer::from_slice(&[0.0f32; 10])?;
+///
+/// unsafe {
+///     // No device supports blocks of a million threads, so this is never launched.
+///     let result = launch_checked!(module.sum<<<1, 1_000_000, 0, stream>>>(
+///         in_x.as_device_ptr(),
+///         in_y.as_device_ptr(),
+///         out.as_device_ptr(),
+///         out.len() as i32
+///     ));
+///     assert!(result.is_err());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! launch_checked {
+    ($module:ident . $function:ident <<<$grid:expr, $block:expr, $shared:expr, $stream:ident>>>( $( $arg:expr),* )) => {
+        {
+            use $crate::error::ResultExt;
+            let name = std::ffi::CString::new(stringify!($function)).unwrap();
+            let function = $module.get_function(&name).context("loading the kernel function");
+            match function {
+                Ok(f) => launch_checked!(f<<<$grid, $block, $shared, $stream>>>( $($arg),* ) ),
+                Err(e) => Err(e),
+            }
+        }
+    };
+    ($function:ident <<<$grid:expr, $block:expr, $shared:expr, $stream:ident>>>( $( $arg:expr),* )) => {
+        {
+            fn assert_impl_devicecopy<T: $crate::memory::DeviceCopy>(_val: T) {}
+            if false {
+                $(
+                    assert_impl_devicecopy($arg);
+                )*
+            };
+
+            $stream.launch_checked(&$function, $grid, $block, $shared,
+                &[
+                    $(
+                        &$arg as *const _ as *mut ::std::ffi::c_void,
+                    )*
+                ]
+            )
+        }
+    };
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::memory::CopyDestination;
-    use crate::memory::DeviceBuffer;
+    use crate::memory::{DeviceBuffer, DevicePointer};
     use crate::quick_init;
-    use crate::stream::{Stream, StreamFlags};
+    use crate::stream::StreamFlags;
+    use crate::test_util::block_on;
     use std::error::Error;
     use std::ffi::CString;
 
@@ -444,4 +986,316 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_launch_async() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        let function = module.get_function(&CString::new("sum")?)?;
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+
+        let mut in_x = DeviceBuffer::from_slice(&[2.0f32; 128])?;
+        let mut in_y = DeviceBuffer::from_slice(&[1.0f32; 128])?;
+        let mut out = DeviceBuffer::from_slice(&[0.0f32; 128])?;
+        let config = function.suggest_launch(128)?;
+        let done = unsafe {
+            let args = (
+                in_x.as_device_ptr(),
+                in_y.as_device_ptr(),
+                out.as_device_ptr(),
+                out.len() as i32,
+            );
+            function.launch_async(&stream, &config, 0, args)?
+        };
+        block_on(done)?;
+
+        let mut out_host = [0f32; 128];
+        out.copy_to(&mut out_host[..])?;
+        for x in out_host.iter() {
+            assert_eq!(3, *x as u32);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_poll_completion() -> Result<(), Box<dyn Error>> {
+        use crate::stream::WakeStrategy;
+        use std::time::Duration;
+
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        let function = module.get_function(&CString::new("sum")?)?;
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+
+        let mut in_x = DeviceBuffer::from_slice(&[2.0f32; 128])?;
+        let mut in_y = DeviceBuffer::from_slice(&[1.0f32; 128])?;
+        let mut out = DeviceBuffer::from_slice(&[0.0f32; 128])?;
+        unsafe {
+            launch!(function<<<1, 128, 0, stream>>>(in_x.as_device_ptr(), in_y.as_device_ptr(), out.as_device_ptr(), out.len() as i32))?;
+        }
+        let strategy = WakeStrategy::Poll {
+            interval: Duration::from_millis(1),
+        };
+        block_on(stream.completion_with(strategy)?)?;
+
+        let mut out_host = [0f32; 128];
+        out.copy_to(&mut out_host[..])?;
+        for x in out_host.iter() {
+            assert_eq!(3, *x as u32);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_attribute() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        let function = module.get_function(&CString::new("sum")?)?;
+
+        assert!(function.get_attribute(FunctionAttribute::NumRegisters)? > 0);
+        assert!(function.get_attribute(FunctionAttribute::MaxThreadsPerBlock)? > 0);
+        assert!(function.get_attribute(FunctionAttribute::PtxVersion)? > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_occupancy() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        let function = module.get_function(&CString::new("sum")?)?;
+
+        let (min_grid_size, block_size) = function.suggested_launch_configuration(0)?;
+        assert!(min_grid_size > 0);
+        assert!(block_size > 0);
+        let warp_size = CurrentContext::get_device()?.get_attribute(DeviceAttribute::WarpSize)?;
+        assert_eq!(0, block_size % warp_size as u32);
+
+        assert!(function.max_active_blocks_per_multiprocessor(block_size, 0)? > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_suggest_launch_covers_all_elements() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        let function = module.get_function(&CString::new("sum")?)?;
+
+        for &total in &[1u32, 127, 128, 1000, 1 << 20] {
+            let config = function.suggest_launch(total)?;
+            let threads = u64::from(config.grid.x) * u64::from(config.block.x);
+            assert!(threads >= u64::from(total));
+            assert!(threads - u64::from(total) < u64::from(config.block.x));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_launch_config_validate() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let device = CurrentContext::get_device()?;
+        let config = |grid: GridSize, block: BlockSize| LaunchConfig { grid, block };
+        let max_grid_y = device.get_attribute(DeviceAttribute::MaxGridDimY)? as u32;
+        let max_shared = device.get_attribute(DeviceAttribute::MaxSharedMemoryPerBlock)? as u32;
+
+        config(GridSize::x(1024), BlockSize::x(128)).validate(device, 0)?;
+
+        let rejected = [
+            (
+                config(GridSize::x(1), BlockSize::x(1_000_000)),
+                0,
+                "block x",
+            ),
+            (config(GridSize::x(1), BlockSize::x(0)), 0, "block x"),
+            (
+                config(GridSize::x(1), BlockSize::xyz(1, 1, 1_000)),
+                0,
+                "block z",
+            ),
+            (
+                config(GridSize::xy(1, max_grid_y + 1), BlockSize::x(1)),
+                0,
+                "grid y",
+            ),
+            (
+                config(GridSize::x(1), BlockSize::xyz(32, 32, 32)),
+                0,
+                "threads",
+            ),
+            (
+                config(GridSize::x(1), BlockSize::x(1)),
+                max_shared + 1,
+                "shared memory",
+            ),
+        ];
+        for (config, shared_mem_bytes, limit) in &rejected {
+            let error = config.validate(device, *shared_mem_bytes).unwrap_err();
+            assert_eq!(CudaError::InvalidValue, error.error());
+            assert!(error.context().contains(limit), "{}", error);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_launch_checked_rejects_oversized_block() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+
+        unsafe {
+            let mut in_x = DeviceBuffer::from_slice(&[2.0f32; 128])?;
+            let mut in_y = DeviceBuffer::from_slice(&[1.0f32; 128])?;
+            let mut out = DeviceBuffer::from_slice(&[0.0f32; 128])?;
+
+            let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+            let result = launch_checked!(module.sum<<<1, 1_000_000, 0, stream>>>(in_x.as_device_ptr(), in_y.as_device_ptr(), out.as_device_ptr(), out.len() as i32));
+            assert_eq!(CudaError::InvalidValue, result.unwrap_err().error());
+
+            launch_checked!(module.sum<<<1, 128, 0, stream>>>(in_x.as_device_ptr(), in_y.as_device_ptr(), out.as_device_ptr(), out.len() as i32))?;
+            stream.synchronize()?;
+
+            let mut out_host = [0f32; 128];
+            out.copy_to(&mut out_host[..])?;
+            for x in out_host.iter() {
+                assert_eq!(3, *x as u32);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_launch_batch() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        let sum = module.get_function(&CString::new("sum")?)?;
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+
+        let mut in_x = DeviceBuffer::from_slice(&[2.0f32; 96])?;
+        let mut in_y = DeviceBuffer::from_slice(&[1.0f32; 96])?;
+        let mut out = DeviceBuffer::from_slice(&[0.0f32; 96])?;
+
+        // One launch per partition of 32 elements.
+        let mut pointers = Vec::new();
+        for i in 0..3 {
+            let start = i * 32;
+            pointers.push([
+                in_x[start..start + 32].as_device_ptr(),
+                in_y[start..start + 32].as_device_ptr(),
+                out[start..start + 32].as_device_ptr(),
+            ]);
+        }
+        let count = 32i32;
+        let config = LaunchConfig {
+            grid: GridSize::x(1),
+            block: BlockSize::x(32),
+        };
+        let args: Vec<[&dyn KernelArg; 4]> = pointers
+            .iter()
+            .map(|p| [&p[0] as &dyn KernelArg, &p[1], &p[2], &count])
+            .collect();
+        let launches: Vec<_> = args
+            .iter()
+            .map(|a| (&sum, config.clone(), &a[..]))
+            .collect();
+
+        unsafe {
+            stream.launch_batch(&launches).map_err(|(_, e)| e)?;
+        }
+        stream.synchronize()?;
+
+        let mut out_host = [0f32; 96];
+        out.copy_to(&mut out_host[..])?;
+        for x in out_host.iter() {
+            assert_eq!(3, *x as u32);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_launch_with_dynamic_shared_memory() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/shared.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+
+        let input: Vec<f32> = (0..64).map(|i| i as f32).collect();
+        let mut in_x = DeviceBuffer::from_slice(&input)?;
+        let mut out = DeviceBuffer::from_slice(&[0.0f32; 64])?;
+        unsafe {
+            launch!(module.reverse<<<1, 64, 64 * 4, stream>>>(in_x.as_device_ptr(), out.as_device_ptr()))?;
+        }
+        stream.synchronize()?;
+
+        let mut out_host = [0f32; 64];
+        out.copy_to(&mut out_host[..])?;
+        for (i, x) in out_host.iter().enumerate() {
+            assert_eq!(63 - i as u32, *x as u32);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_launch_passes_shared_memory_size() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/shared.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+
+        let mut out = DeviceBuffer::from_slice(&[u32::MAX])?;
+        for &shared in &[0u32, 256, 4096] {
+            unsafe {
+                launch!(module.dynamic_smem_size<<<1, 1, shared, stream>>>(out.as_device_ptr()))?;
+            }
+            stream.synchronize()?;
+            assert_eq!(vec![shared], out.copy_to_vec()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_launch() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let ptx_text = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_string(&ptx_text)?;
+        type SumArgs = (
+            DevicePointer<f32>,
+            DevicePointer<f32>,
+            DevicePointer<f32>,
+            i32,
+        );
+        let sum: TypedFunction<SumArgs> =
+            TypedFunction::new(module.get_function(&CString::new("sum")?)?);
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+
+        let mut in_x = DeviceBuffer::from_slice(&[2.0f32; 128])?;
+        let mut in_y = DeviceBuffer::from_slice(&[1.0f32; 128])?;
+        let mut out = DeviceBuffer::from_slice(&[0.0f32; 128])?;
+        unsafe {
+            sum.launch(
+                &stream,
+                1,
+                128,
+                0,
+                (
+                    in_x.as_device_ptr(),
+                    in_y.as_device_ptr(),
+                    out.as_device_ptr(),
+                    128,
+                ),
+            )?;
+        }
+        stream.synchronize()?;
+
+        let mut out_host = [0f32; 128];
+        out.copy_to(&mut out_host[..])?;
+        for x in out_host.iter() {
+            assert_eq!(3, *x as u32);
+        }
+        Ok(())
+    }
 }
@@ -164,18 +164,25 @@ pub use rustacuda_derive::*;
 pub mod context;
 pub mod device;
 pub mod error;
+pub mod event;
 pub mod function;
 pub mod memory;
 pub mod module;
+pub mod pipeline;
 pub mod prelude;
 pub mod stream;
 
 mod derive_compile_fail;
+#[cfg(test)]
+mod test_util;
 
 use crate::context::{Context, ContextFlags};
-use crate::device::Device;
-use crate::error::{CudaResult, ToResult};
+use crate::device::{Device, DeviceAttribute};
+use crate::error::{CudaError, CudaResult, ToResult};
 use cuda_sys::cuda::{cuDriverGetVersion, cuInit};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static FORCE_SYNCHRONOUS: AtomicBool = AtomicBool::new(false);
 
 bitflags! {
     /// Bit flags for initializing the CUDA driver. Currently, no flags are defined,
@@ -195,6 +202,26 @@ bitflags! {
 ///
 /// The `flags` parameter is used to configure the CUDA API. Currently no flags are defined, so
 /// it must be `CudaFlags::empty()`.
+///
+/// This only initializes the driver; it does not create a context. It is safe to call more than
+/// once. After initializing, devices can be enumerated and queried (see
+/// [`Device::devices`](device/struct.Device.html#method.devices)), which is useful for choosing a
+/// device before creating a context on it. Most other operations, such as allocating memory or
+/// loading modules, still require a current context.
+///
+/// # Examples:
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use rustacuda::device::Device;
+/// rustacuda::init(rustacuda::CudaFlags::empty())?;
+/// for device in Device::devices()? {
+///     println!("Found device: {}", device?.name()?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
 pub fn init(flags: CudaFlags) -> CudaResult<()> {
     unsafe { cuInit(flags.bits()).to_result() }
 }
@@ -211,6 +238,77 @@ pub fn quick_init() -> CudaResult<Context> {
     Context::create_and_push(ContextFlags::MAP_HOST | ContextFlags::SCHED_AUTO, device)
 }
 
+/// Shortcut for initializing the CUDA Driver API and creating a CUDA context with default settings
+/// for the first device with at least the given compute capability.
+///
+/// This behaves like `quick_init`, except that devices with a compute capability lower than
+/// `major.minor` are skipped. This allows programs to fail early with a clear error rather than
+/// failing later when launching a kernel the device cannot run.
+///
+/// # Errors:
+///
+/// If there are no devices at all, returns `CudaError::NoDevice`. If there are devices but none
+/// of them has the required compute capability, returns `CudaError::NotSupported`. Otherwise
+/// returns any error from initializing CUDA or creating the context.
+///
+/// # Example:
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// // Requires a device with compute capability 3.0 or higher
+/// let _context = rustacuda::quick_init_min_cc(3, 0)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn quick_init_min_cc(major: u32, minor: u32) -> CudaResult<Context> {
+    init(CudaFlags::empty())?;
+    let devices = Device::devices()?;
+    if devices.len() == 0 {
+        return Err(CudaError::NoDevice);
+    }
+    for device in devices {
+        let device = device?;
+        let device_major = device.get_attribute(DeviceAttribute::ComputeCapabilityMajor)?;
+        let device_minor = device.get_attribute(DeviceAttribute::ComputeCapabilityMinor)?;
+        if (device_major as u32, device_minor as u32) >= (major, minor) {
+            return Context::create_and_push(
+                ContextFlags::MAP_HOST | ContextFlags::SCHED_AUTO,
+                device,
+            );
+        }
+    }
+    Err(CudaError::NotSupported)
+}
+
+/// Make every asynchronous operation synchronize its stream before returning.
+///
+/// When enabled, asynchronous copies, prefetches and kernel launches wait for their stream to
+/// finish before returning, so their effects are visible as soon as the call completes. This
+/// sacrifices all overlap between host and device work, but it makes execution deterministic,
+/// which is useful for tracking down missing synchronization. It also means that errors from
+/// asynchronous work are reported by the call that caused them.
+///
+/// The setting is global to the process and can be changed at any time.
+///
+/// # Examples:
+///
+/// ```
+/// rustacuda::set_force_synchronous(true);
+/// // ... debug some asynchronous code
+/// rustacuda::set_force_synchronous(false);
+/// ```
+pub fn set_force_synchronous(enabled: bool) {
+    FORCE_SYNCHRONOUS.store(enabled, Ordering::SeqCst);
+}
+
+/// Returns true if asynchronous operations are being forced to synchronize.
+///
+/// See [`set_force_synchronous`](fn.set_force_synchronous.html).
+pub fn is_force_synchronous() -> bool {
+    FORCE_SYNCHRONOUS.load(Ordering::SeqCst)
+}
+
 /// Struct representing the CUDA API version number.
 #[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
 pub struct CudaApiVersion {
@@ -255,6 +353,30 @@ mod test {
         init(CudaFlags::empty()).unwrap();
         init(CudaFlags::empty()).unwrap();
     }
+
+    #[test]
+    fn test_enumerate_devices_without_context() {
+        init(CudaFlags::empty()).unwrap();
+        let devices: Vec<Device> = Device::devices().unwrap().map(Result::unwrap).collect();
+        assert!(!devices.is_empty());
+        for device in devices {
+            assert!(!device.name().unwrap().is_empty());
+            assert!(device.total_memory().unwrap() > 0);
+        }
+    }
+
+    #[test]
+    fn test_quick_init_min_cc() {
+        let _context = quick_init_min_cc(1, 0).unwrap();
+    }
+
+    #[test]
+    fn test_quick_init_min_cc_too_high() {
+        assert_eq!(
+            CudaError::NotSupported,
+            quick_init_min_cc(99, 0).unwrap_err()
+        );
+    }
 }
 
 // Fake module with a private trait used to prevent outside code from implementing certain traits.
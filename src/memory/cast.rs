@@ -0,0 +1,108 @@
+use super::DeviceCopy;
+use std::error::Error;
+use std::fmt;
+use std::slice;
+
+/// Error returned by [`cast_slice`](fn.cast_slice.html) when a slice can't be reinterpreted as the
+/// requested type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum CastError {
+    /// The slice is not aligned correctly for the target type.
+    Misaligned,
+    /// The length of the slice in bytes is not a multiple of the size of the target type, or
+    /// exactly one of the types is zero-sized.
+    SizeMismatch,
+}
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CastError::Misaligned => write!(f, "Slice is misaligned for the target type"),
+            CastError::SizeMismatch => {
+                write!(
+                    f,
+                    "Slice length is not a multiple of the target type's size"
+                )
+            }
+        }
+    }
+}
+impl Error for CastError {}
+
+/// Reinterpret a host slice of `A` as a slice of `B`, such as `&[f32]` as `&[u8]`.
+///
+/// This is useful for preparing host data for upload with
+/// [`DeviceBuffer::from_slice`](struct.DeviceBuffer.html#method.from_slice) when the kernel
+/// expects a different element type. The length of the result is the length of `a` in bytes
+/// divided by the size of `B`.
+///
+/// # Errors
+///
+/// Returns `CastError::Misaligned` if `a` is not aligned for `B`, and `CastError::SizeMismatch`
+/// if the length of `a` in bytes is not a multiple of the size of `B`.
+///
+/// # Safety
+///
+/// `DeviceCopy` does not guarantee that every bit pattern is a valid value, so the caller must
+/// ensure that the bytes of `a` form valid values of `B`. This always holds when `B` is an integer
+/// or floating-point type, but not when `B` is, for example, `bool`.
+///
+/// # Examples
+///
+/// ```
+/// use rustacuda::memory::*;
+/// let floats = [1.0f32, 2.0];
+/// let bytes: &[u8] = unsafe { cast_slice(&floats).unwrap() };
+/// assert_eq!(8, bytes.len());
+/// ```
+pub unsafe fn cast_slice<A: DeviceCopy, B: DeviceCopy>(a: &[A]) -> Result<&[B], CastError> {
+    let size_a = size_of::<A>();
+    let size_b = size_of::<B>();
+    if size_a == size_b && align_of::<A>() >= align_of::<B>() {
+        return Ok(slice::from_raw_parts(a.as_ptr() as *const B, a.len()));
+    }
+    if size_a == 0 || size_b == 0 {
+        return Err(CastError::SizeMismatch);
+    }
+    if !(a.as_ptr() as usize).is_multiple_of(align_of::<B>()) {
+        return Err(CastError::Misaligned);
+    }
+
+    let bytes = size_of_val(a);
+    if !bytes.is_multiple_of(size_b) {
+        return Err(CastError::SizeMismatch);
+    }
+    Ok(slice::from_raw_parts(
+        a.as_ptr() as *const B,
+        bytes / size_b,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cast_f32_to_u8() {
+        let floats = [1.0f32, -2.5];
+        let bytes: &[u8] = unsafe { cast_slice(&floats).unwrap() };
+        assert_eq!(8, bytes.len());
+        assert_eq!(&1.0f32.to_bits().to_ne_bytes()[..], &bytes[0..4]);
+        assert_eq!(&(-2.5f32).to_bits().to_ne_bytes()[..], &bytes[4..8]);
+    }
+
+    #[test]
+    fn test_cast_misaligned() {
+        let words = [0u32; 2];
+        let bytes: &[u8] = unsafe { cast_slice(&words).unwrap() };
+        let result: Result<&[f32], _> = unsafe { cast_slice(&bytes[1..5]) };
+        assert_eq!(Err(CastError::Misaligned), result);
+    }
+
+    #[test]
+    fn test_cast_size_mismatch() {
+        let words = [0u32; 2];
+        let bytes: &[u8] = unsafe { cast_slice(&words).unwrap() };
+        let result: Result<&[f32], _> = unsafe { cast_slice(&bytes[0..6]) };
+        assert_eq!(Err(CastError::SizeMismatch), result);
+    }
+}
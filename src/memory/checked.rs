@@ -0,0 +1,112 @@
+use super::DeviceCopy;
+#[cfg(all(feature = "strict-device-copy", debug_assertions))]
+use super::DeviceSlice;
+#[cfg(all(feature = "strict-device-copy", debug_assertions))]
+use crate::error::{CudaResult, ToResult};
+#[cfg(all(feature = "strict-device-copy", debug_assertions))]
+use cuda_sys::cuda;
+#[cfg(all(feature = "strict-device-copy", debug_assertions))]
+use std::os::raw::c_void;
+
+/// A `DeviceCopy` type which can describe the layout the device code expects it to have.
+///
+/// Rust and the device code each decide the layout of a struct independently, so a missing
+/// `#[repr(C)]`, fields declared in a different order or a field of the wrong width make the
+/// kernel read garbage without any error. Implementing this trait lets
+/// [`DeviceBuffer::from_slice_checked`](struct.DeviceBuffer.html#method.from_slice_checked)
+/// compare what was actually uploaded against the layout declared here.
+///
+/// The check only runs in debug builds with the `strict-device-copy` feature enabled. Otherwise
+/// `from_slice_checked` is the same as `from_slice`.
+///
+/// # Examples:
+///
+/// ```
+/// use rustacuda::memory::*;
+///
+/// // Matches `struct Particle { unsigned char kind; float mass; };` on the device.
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Particle {
+///     kind: u8,
+///     mass: f32,
+/// }
+/// unsafe impl DeviceCopy for Particle {}
+///
+/// impl CheckedDeviceCopy for Particle {
+///     const DEVICE_SIZE: usize = 8;
+///     const DEVICE_ALIGN: usize = 4;
+///
+///     fn encode_device(&self, bytes: &mut [u8]) {
+///         bytes[0] = self.kind;
+///         bytes[4..8].copy_from_slice(&self.mass.to_bits().to_ne_bytes());
+///     }
+/// }
+/// ```
+pub trait CheckedDeviceCopy: DeviceCopy {
+    /// The size in bytes of the type in the device code.
+    const DEVICE_SIZE: usize;
+    /// The alignment in bytes of the type in the device code.
+    const DEVICE_ALIGN: usize;
+
+    /// Write `self` into `bytes` in the device code's layout, with each field at the offset the
+    /// device code expects it. `bytes` is `DEVICE_SIZE` bytes long.
+    ///
+    /// Bytes which aren't written are treated as padding and are not checked.
+    fn encode_device(&self, bytes: &mut [u8]);
+}
+
+// Check that the contents of `uploaded`, which were just copied from `values`, match the layout
+// declared by `T`, panicking on the first mismatch.
+//
+// The bytes are compared after a round trip through the device rather than by reading `values`
+// directly, since the padding bytes of host values are uninitialized.
+#[cfg(all(feature = "strict-device-copy", debug_assertions))]
+pub(crate) fn check_device_layout<T: CheckedDeviceCopy>(
+    values: &[T],
+    uploaded: &DeviceSlice<T>,
+) -> CudaResult<()> {
+    let size = size_of::<T>();
+    assert!(
+        size == T::DEVICE_SIZE && align_of::<T>() == T::DEVICE_ALIGN,
+        "DeviceCopy layout mismatch: size {} and alignment {} on the host but {} and {} on the \
+         device",
+        size,
+        align_of::<T>(),
+        T::DEVICE_SIZE,
+        T::DEVICE_ALIGN
+    );
+
+    let mut downloaded = vec![0u8; size_of_val(values)];
+    if !downloaded.is_empty() {
+        unsafe {
+            cuda::cuMemcpyDtoH_v2(
+                downloaded.as_mut_ptr() as *mut c_void,
+                uploaded.as_ptr() as u64,
+                downloaded.len(),
+            )
+            .to_result()?;
+        }
+    }
+
+    // Encode each value over two different fills, so that bytes which `encode_device` doesn't
+    // write (the padding) can be told apart from the ones it does.
+    let mut low = vec![0x00u8; size];
+    let mut high = vec![0xFFu8; size];
+    for (index, (value, actual)) in values.iter().zip(downloaded.chunks(size)).enumerate() {
+        low.iter_mut().for_each(|b| *b = 0x00);
+        high.iter_mut().for_each(|b| *b = 0xFF);
+        value.encode_device(&mut low);
+        value.encode_device(&mut high);
+        for byte in 0..size {
+            if low[byte] == high[byte] && low[byte] != actual[byte] {
+                panic!(
+                    "DeviceCopy layout mismatch: byte {} of element {} is {:#04x} on the device \
+                     but {:#04x} in the declared layout",
+                    byte, index, actual[byte], low[byte]
+                );
+            }
+        }
+    }
+    Ok(())
+}
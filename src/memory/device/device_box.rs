@@ -1,11 +1,13 @@
 use crate::error::{CudaResult, DropResult, ToResult};
-use crate::memory::device::CopyDestination;
+use crate::memory::device::{AnyDeviceBuffer, CopyDestination, DeviceSlice};
 use crate::memory::malloc::{cuda_free, cuda_malloc};
 use crate::memory::DeviceCopy;
 use crate::memory::DevicePointer;
-use cuda_sys::cuda;
+use crate::memory::Zeroable;
+use cuda_sys::cuda::{self, CUdeviceptr};
 use std::fmt::{self, Pointer};
 use std::mem;
+use std::ptr;
 
 use std::os::raw::c_void;
 
@@ -37,6 +39,29 @@ impl<T: DeviceCopy> DeviceBox<T> {
         dev_box.copy_from(val)?;
         Ok(dev_box)
     }
+
+    /// Copy the boxed value into `dst`, without allocating any host memory.
+    ///
+    /// This is a synchronous copy, equivalent to [`copy_to`](trait.CopyDestination.html#tymethod.copy_to),
+    /// which is useful for reusing existing host storage.
+    ///
+    /// # Errors:
+    ///
+    /// If a CUDA error occurs, return the error.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let five = DeviceBox::new(&5).unwrap();
+    /// let mut host = 0;
+    /// five.read_into(&mut host).unwrap();
+    /// assert_eq!(5, host);
+    /// ```
+    pub fn read_into(&self, dst: &mut T) -> CudaResult<()> {
+        self.copy_to(dst)
+    }
 }
 impl<T> DeviceBox<T> {
     /// Allocate device memory, but do not initialize it.
@@ -72,33 +97,35 @@ impl<T> DeviceBox<T> {
     ///
     /// This doesn't actually allocate if `T` is zero-sized.
     ///
-    /// # Safety:
-    ///
-    /// The backing memory is zeroed, which may not be a valid bit-pattern for type `T`. The caller
-    /// must ensure either that all-zeroes is a valid bit-pattern for type `T` or that the backing
-    /// memory is set to a valid value before it is read.
+    /// This requires `T` to implement [`Zeroable`](trait.Zeroable.html), which guarantees that
+    /// all-zeroes is a valid bit-pattern for `T`.
     ///
     /// # Examples:
     ///
     /// ```
     /// # let _context = rustacuda::quick_init().unwrap();
     /// use rustacuda::memory::*;
-    /// let mut zero = unsafe { DeviceBox::zeroed().unwrap() };
+    /// let mut zero = DeviceBox::zeroed().unwrap();
     /// let mut value = 5u64;
     /// zero.copy_to(&mut value).unwrap();
     /// assert_eq!(0, value);
     /// ```
-    pub unsafe fn zeroed() -> CudaResult<Self> {
-        let mut new_box = DeviceBox::uninitialized()?;
-        if mem::size_of::<T>() != 0 {
-            cuda::cuMemsetD8_v2(
-                new_box.as_device_ptr().as_raw_mut() as u64,
-                0,
-                mem::size_of::<T>(),
-            )
-            .to_result()?;
+    pub fn zeroed() -> CudaResult<Self>
+    where
+        T: Zeroable,
+    {
+        unsafe {
+            let mut new_box = DeviceBox::uninitialized()?;
+            if size_of::<T>() != 0 {
+                cuda::cuMemsetD8_v2(
+                    new_box.as_device_ptr().as_raw_mut() as u64,
+                    0,
+                    size_of::<T>(),
+                )
+                .to_result()?;
+            }
+            Ok(new_box)
         }
-        Ok(new_box)
     }
 
     /// Constructs a DeviceBox from a raw pointer.
@@ -234,6 +261,29 @@ impl<T> DeviceBox<T> {
         }
     }
 }
+impl<T: DeviceCopy, const N: usize> DeviceBox<[T; N]> {
+    /// Returns a view of the boxed array as a device slice of `N` elements.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let x = DeviceBox::new(&[1u32, 2, 3, 4]).unwrap();
+    /// let mut host_values = [0u32; 4];
+    /// x.as_device_slice().copy_to(&mut host_values[..]).unwrap();
+    /// assert_eq!([1u32, 2, 3, 4], host_values);
+    /// ```
+    pub fn as_device_slice(&self) -> &DeviceSlice<T> {
+        // Zero-sized boxes don't allocate, but slices may not be built from a null pointer.
+        let ptr = if self.ptr.is_null() {
+            unsafe { DevicePointer::wrap(ptr::NonNull::dangling().as_ptr()) }
+        } else {
+            self.ptr.cast::<T>()
+        };
+        unsafe { DeviceSlice::from_raw_parts(ptr, N) }
+    }
+}
 impl<T> Drop for DeviceBox<T> {
     fn drop(&mut self) {
         if self.ptr.is_null() {
@@ -253,6 +303,19 @@ impl<T> Pointer for DeviceBox<T> {
     }
 }
 impl<T> crate::private::Sealed for DeviceBox<T> {}
+impl<T> AnyDeviceBuffer for DeviceBox<T> {
+    fn len_bytes(&self) -> usize {
+        size_of::<T>()
+    }
+
+    fn device_ptr_raw(&self) -> CUdeviceptr {
+        self.ptr.as_raw() as CUdeviceptr
+    }
+
+    fn element_size(&self) -> usize {
+        size_of::<T>()
+    }
+}
 impl<T: DeviceCopy> CopyDestination<T> for DeviceBox<T> {
     fn copy_from(&mut self, val: &T) -> CudaResult<()> {
         let size = mem::size_of::<T>();
@@ -341,6 +404,15 @@ mod test_device_box {
         let _ = unsafe { DeviceBox::from_device(ptr) };
     }
 
+    #[test]
+    fn test_zeroed() {
+        let _context = crate::quick_init().unwrap();
+        let zero = DeviceBox::<[u64; 4]>::zeroed().unwrap();
+        let mut value = [0xFFu64; 4];
+        zero.copy_to(&mut value).unwrap();
+        assert_eq!([0u64; 4], value);
+    }
+
     #[test]
     fn test_into_from_device() {
         let _context = crate::quick_init().unwrap();
@@ -360,6 +432,39 @@ mod test_device_box {
         assert_eq!(y, z);
     }
 
+    #[test]
+    fn test_copy_struct_value() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Particle {
+            position: [f32; 3],
+            mass: f64,
+            id: u32,
+        }
+        unsafe impl DeviceCopy for Particle {}
+
+        let _context = crate::quick_init().unwrap();
+        let particle = Particle {
+            position: [1.0, 2.0, 3.0],
+            mass: 4.5,
+            id: 7,
+        };
+        let mut device = DeviceBox::new(&Particle {
+            position: [0.0; 3],
+            mass: 0.0,
+            id: 0,
+        })
+        .unwrap();
+        device.copy_from(&particle).unwrap();
+
+        let mut host = Particle {
+            position: [0.0; 3],
+            mass: 0.0,
+            id: 0,
+        };
+        device.copy_to(&mut host).unwrap();
+        assert_eq!(particle, host);
+    }
+
     #[test]
     fn test_copy_device_to_host() {
         let _context = crate::quick_init().unwrap();
@@ -369,6 +474,78 @@ mod test_device_box {
         assert_eq!(5, y);
     }
 
+    #[test]
+    fn test_array_as_device_slice() {
+        let _context = crate::quick_init().unwrap();
+        let x = DeviceBox::new(&[0u32, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+        let slice = x.as_device_slice();
+        assert_eq!(8, slice.len());
+        let mut host = [0u32; 8];
+        slice.copy_to(&mut host[..]).unwrap();
+        assert_eq!([0u32, 1, 2, 3, 4, 5, 6, 7], host);
+    }
+
+    #[test]
+    fn test_large_array() {
+        let _context = crate::quick_init().unwrap();
+        let mut value = [0u8; 100];
+        for (i, byte) in value.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let x = DeviceBox::new(&value).unwrap();
+        let mut y = [0u8; 100];
+        x.copy_to(&mut y).unwrap();
+        assert_eq!(&value[..], &y[..]);
+    }
+
+    #[test]
+    fn test_read_into() {
+        let _context = crate::quick_init().unwrap();
+        let x = DeviceBox::new(&(5u64, 7u32)).unwrap();
+        let mut y = (0u64, 0u32);
+        x.read_into(&mut y).unwrap();
+        assert_eq!((5, 7), y);
+    }
+
+    #[test]
+    fn test_tuples() {
+        let _context = crate::quick_init().unwrap();
+        let x = DeviceBox::new(&(1.5f32, 7u32, -3i64)).unwrap();
+        let mut y = (0.0f32, 0u32, 0i64);
+        x.copy_to(&mut y).unwrap();
+        assert_eq!((1.5, 7, -3), y);
+
+        let x = DeviceBox::new(&(1u8,)).unwrap();
+        let mut y = (0u8,);
+        x.copy_to(&mut y).unwrap();
+        assert_eq!((1,), y);
+
+        let twelve = (
+            1u8, 2u16, 3u32, 4u64, 5i8, 6i16, 7i32, 8i64, 9f32, 10f64, true, 'c',
+        );
+        let x = DeviceBox::new(&twelve).unwrap();
+        let mut y = (
+            0u8, 0u16, 0u32, 0u64, 0i8, 0i16, 0i32, 0i64, 0f32, 0f64, false, 'a',
+        );
+        x.copy_to(&mut y).unwrap();
+        assert_eq!(twelve, y);
+    }
+
+    #[test]
+    fn test_zeroed_tuples() {
+        let _context = crate::quick_init().unwrap();
+        let x = DeviceBox::<(u32,)>::zeroed().unwrap();
+        let mut y = (5u32,);
+        x.copy_to(&mut y).unwrap();
+        assert_eq!((0,), y);
+
+        type Twelve = (u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, usize, isize);
+        let x = DeviceBox::<Twelve>::zeroed().unwrap();
+        let mut y: Twelve = (1, 2, 3, 4, 5, 6, 7, 8, 9.0, 10.0, 11, 12);
+        x.copy_to(&mut y).unwrap();
+        assert_eq!((0, 0, 0, 0, 0, 0, 0, 0, 0.0, 0.0, 0, 0), y);
+    }
+
     #[test]
     fn test_copy_device_to_device() {
         let _context = crate::quick_init().unwrap();
@@ -1,11 +1,24 @@
 use crate::error::{CudaError, CudaResult, DropResult, ToResult};
-use crate::memory::device::{CopyDestination, DeviceSlice};
+use crate::event::{Event, EventFlags};
+#[cfg(all(feature = "strict-device-copy", debug_assertions))]
+use crate::memory::checked::check_device_layout;
+use crate::memory::device::{
+    AnyDeviceBuffer, AsyncCopyDestination, CopyDestination, DeviceBox, DeviceSlice,
+};
 use crate::memory::malloc::{cuda_free, cuda_malloc};
+use crate::memory::CheckedDeviceCopy;
 use crate::memory::DeviceCopy;
 use crate::memory::DevicePointer;
-use cuda_sys::cuda;
+use crate::memory::LockedBuffer;
+use crate::memory::Zeroable;
+use crate::stream::{Stream, StreamCompletion};
+use cuda_sys::cuda::{self, CUdeviceptr};
+use std::future::Future;
 use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::os::raw::c_void;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 use std::ptr;
 
@@ -61,34 +74,35 @@ impl<T> DeviceBuffer<T> {
     /// If the allocation fails, returns the error from CUDA. If `size` is large enough that
     /// `size * mem::sizeof::<T>()` overflows usize, then returns InvalidMemoryAllocation.
     ///
-    /// # Safety:
-    ///
-    /// The backing memory is zeroed, which may not be a valid bit-pattern for type `T`. The caller
-    /// must ensure either that all-zeroes is a valid bit-pattern for type `T` or that the backing
-    /// memory is set to a valid value before it is read.
+    /// This requires `T` to implement [`Zeroable`](trait.Zeroable.html), which guarantees that
+    /// all-zeroes is a valid bit-pattern for `T`.
     ///
     /// # Examples:
     ///
     /// ```
     /// # let _context = rustacuda::quick_init().unwrap();
     /// use rustacuda::memory::*;
-    /// let buffer = unsafe { DeviceBuffer::zeroed(5).unwrap() };
+    /// let buffer = DeviceBuffer::zeroed(5).unwrap();
     /// let mut host_values = [1u64, 2, 3, 4, 5];
     /// buffer.copy_to(&mut host_values).unwrap();
     /// assert_eq!([0u64, 0, 0, 0, 0], host_values);
     /// ```
-    pub unsafe fn zeroed(size: usize) -> CudaResult<Self> {
+    pub fn zeroed(size: usize) -> CudaResult<Self>
+    where
+        T: Zeroable,
+    {
         let bytes = size
             .checked_mul(mem::size_of::<T>())
             .ok_or(CudaError::InvalidMemoryAllocation)?;
 
         let ptr = if bytes > 0 {
-            let mut ptr = cuda_malloc(bytes)?;
-            cuda::cuMemsetD8_v2(ptr.as_raw_mut() as u64, 0, size * mem::size_of::<T>())
-                .to_result()?;
-            ptr
+            unsafe {
+                let mut ptr = cuda_malloc(bytes)?;
+                cuda::cuMemsetD8_v2(ptr.as_raw_mut() as u64, 0, bytes).to_result()?;
+                ptr
+            }
         } else {
-            DevicePointer::wrap(ptr::NonNull::dangling().as_ptr() as *mut T)
+            unsafe { DevicePointer::wrap(ptr::NonNull::dangling().as_ptr()) }
         };
         Ok(DeviceBuffer {
             buf: ptr,
@@ -136,6 +150,53 @@ impl<T> DeviceBuffer<T> {
         DeviceBuffer { buf: ptr, capacity }
     }
 
+    /// Returns a device pointer to the element at `index`.
+    ///
+    /// The caller must ensure that the buffer outlives the returned pointer, and that the pointer
+    /// is not dereferenced by the CPU.
+    ///
+    /// # Errors:
+    ///
+    /// If `index` is out of bounds, returns InvalidValue.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let buffer = DeviceBuffer::from_slice(&[0u64, 1, 2, 3, 4]).unwrap();
+    /// let ptr = buffer.element_ptr(3).unwrap();
+    /// assert!(buffer.element_ptr(5).is_err());
+    /// ```
+    pub fn element_ptr(&self, index: usize) -> CudaResult<DevicePointer<T>> {
+        if index >= self.capacity {
+            return Err(CudaError::InvalidValue);
+        }
+        Ok(self.buf.wrapping_add(index))
+    }
+
+    /// Returns true if `ptr` points into this buffer.
+    ///
+    /// That is, if the address of `ptr` lies within `[base, base + len * mem::size_of::<T>())`.
+    /// This only compares addresses; it does not check that `ptr` is aligned to an element
+    /// boundary. An empty buffer contains no pointers.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let buffer = DeviceBuffer::from_slice(&[0u64, 1, 2, 3, 4]).unwrap();
+    /// let ptr = buffer.element_ptr(3).unwrap();
+    /// assert!(buffer.contains_ptr(ptr));
+    /// ```
+    pub fn contains_ptr(&self, ptr: DevicePointer<T>) -> bool {
+        let base = self.buf.as_raw() as usize;
+        let end = base + self.capacity * size_of::<T>();
+        let addr = ptr.as_raw() as usize;
+        base <= addr && addr < end
+    }
+
     /// Destroy a `DeviceBuffer`, returning an error.
     ///
     /// Deallocating device memory can return errors from previous asynchronous work. This function
@@ -200,7 +261,366 @@ impl<T: DeviceCopy> DeviceBuffer<T> {
             Ok(uninit)
         }
     }
+
+    /// Allocate a new device buffer and asynchronously upload a copy of `src` into it on
+    /// `stream`.
+    ///
+    /// `src` is first copied into an internal page-locked staging buffer, so it can be reused or
+    /// dropped as soon as this returns. The staging buffer is owned by the returned future, which
+    /// resolves once the upload (and any earlier work on `stream`) has completed.
+    ///
+    /// Work queued on `stream` after this call, such as a kernel launch, is ordered after the
+    /// upload, so the buffer can be used on the same stream immediately. Work on other streams,
+    /// and synchronous copies, must wait for the future to resolve.
+    ///
+    /// If the future is dropped before it resolves, it blocks until the upload has completed so
+    /// that the staging buffer is not freed while the device is reading it. The stream's context
+    /// must be current on the thread which drops the future.
+    ///
+    /// # Errors:
+    ///
+    /// If an allocation or the copy fails, returns the error from CUDA. The future resolves to the
+    /// error from any failed work on the stream.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+    /// let (buffer, uploaded) = DeviceBuffer::upload_async(&[1u32, 2, 3], &stream).unwrap();
+    /// // ... enqueue kernels using `buffer` on `stream`, then in asynchronous code:
+    /// // uploaded.await?;
+    /// # stream.synchronize().unwrap();
+    /// # drop(uploaded);
+    /// ```
+    pub fn upload_async(
+        src: &[T],
+        stream: &Stream,
+    ) -> CudaResult<(DeviceBuffer<T>, UploadCompletion<T>)> {
+        let staging = unsafe {
+            let mut staging = LockedBuffer::uninitialized(src.len())?;
+            ptr::copy_nonoverlapping(src.as_ptr(), staging.as_mut_ptr(), src.len());
+            staging
+        };
+        let mut buffer = unsafe { DeviceBuffer::uninitialized(src.len())? };
+        let copied = Event::new(EventFlags::DISABLE_TIMING)?;
+        unsafe {
+            buffer.async_copy_from(&staging, stream)?;
+        }
+        copied.record(stream)?;
+        let completion = stream.completion()?;
+        Ok((
+            buffer,
+            UploadCompletion {
+                completion,
+                staging: Some(staging),
+                copied,
+            },
+        ))
+    }
+
+    /// Overwrite the contents of this buffer with a copy of `src`, reusing this buffer's
+    /// allocation.
+    ///
+    /// This is the device-memory equivalent of `Clone::clone_from`: rather than allocating a new
+    /// buffer, the data is copied device-to-device into the existing one. Device-to-device copies
+    /// don't block the host, so the copy may still be in progress when this returns. Like
+    /// [`broadcast_from`](#method.broadcast_from), it is ordered before later synchronous copies
+    /// but not before work on non-blocking streams.
+    ///
+    /// # Errors:
+    ///
+    /// If the buffers have different lengths, returns InvalidValue. If the copy fails, returns the
+    /// error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let src = DeviceBuffer::from_slice(&[1u32, 2, 3]).unwrap();
+    /// let mut dst = DeviceBuffer::from_slice(&[0u32; 3]).unwrap();
+    /// dst.clone_from_device(&src).unwrap();
+    /// let mut host_values = [0u32; 3];
+    /// dst.copy_to(&mut host_values).unwrap();
+    /// assert_eq!([1, 2, 3], host_values);
+    /// ```
+    pub fn clone_from_device(&mut self, src: &DeviceBuffer<T>) -> CudaResult<()> {
+        if self.len() != src.len() {
+            return Err(CudaError::InvalidValue);
+        }
+        self.copy_from(&**src)
+    }
+
+    /// Overwrite every element of this buffer with a copy of the element at `index`.
+    ///
+    /// Elements of one, two or four bytes are filled with a single `cuMemsetD8`, `cuMemsetD16` or
+    /// `cuMemsetD32`, which only requires reading the one element back to the host. Larger
+    /// elements are filled entirely on the device by copying the element to the front of the
+    /// buffer and then repeatedly doubling the filled prefix with device-to-device copies, which
+    /// takes about `log2(len)` copies.
+    ///
+    /// The fill doesn't block the host, and may still be running on the device when this returns.
+    /// It is queued on the legacy default stream, so later synchronous copies from the buffer see
+    /// the filled values, but work on streams created with `StreamFlags::NON_BLOCKING` isn't
+    /// ordered after it. Synchronize the context before using the buffer from such a stream.
+    ///
+    /// # Errors:
+    ///
+    /// If `index` is out of bounds, returns InvalidValue. If a copy or fill fails, returns the
+    /// error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut buffer = DeviceBuffer::from_slice(&[1u32, 2, 3, 4]).unwrap();
+    /// buffer.broadcast_from(2).unwrap();
+    /// let mut host_values = [0u32; 4];
+    /// buffer.copy_to(&mut host_values).unwrap();
+    /// assert_eq!([3, 3, 3, 3], host_values);
+    /// ```
+    pub fn broadcast_from(&mut self, index: usize) -> CudaResult<()> {
+        if index >= self.capacity {
+            return Err(CudaError::InvalidValue);
+        }
+        let size = size_of::<T>();
+        if size == 0 || self.capacity == 1 {
+            return Ok(());
+        }
+
+        let base = self.buf.as_raw_mut() as u64;
+        let element = base + (index * size) as u64;
+        unsafe {
+            match size {
+                1 => {
+                    let mut value = 0u8;
+                    read_element(&mut value, element)?;
+                    cuda::cuMemsetD8_v2(base, value, self.capacity).to_result()
+                }
+                2 => {
+                    let mut value = 0u16;
+                    read_element(&mut value, element)?;
+                    cuda::cuMemsetD16_v2(base, value, self.capacity).to_result()
+                }
+                4 => {
+                    let mut value = 0u32;
+                    read_element(&mut value, element)?;
+                    cuda::cuMemsetD32_v2(base, value, self.capacity).to_result()
+                }
+                _ => {
+                    if index != 0 {
+                        cuda::cuMemcpyDtoD_v2(base, element, size).to_result()?;
+                    }
+                    // Each copy reads only the filled prefix and writes just past it, so the
+                    // source and destination never overlap.
+                    let mut filled = 1;
+                    while filled < self.capacity {
+                        let count = filled.min(self.capacity - filled);
+                        cuda::cuMemcpyDtoD_v2(base + (filled * size) as u64, base, count * size)
+                            .to_result()?;
+                        filled += count;
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Fill this buffer with copies of the value in `value`, which is already on the device.
+    ///
+    /// This is useful for broadcasting a scalar computed by a kernel without copying it back to
+    /// the host first. The value is copied into the first element and then broadcast as in
+    /// [`broadcast_from`](#method.broadcast_from), so elements of one, two or four bytes are
+    /// filled with a single memset after reading the value back, and larger elements
+    /// are filled entirely on the device. The fill may still be running when this returns; see
+    /// `broadcast_from` for which work is ordered after it.
+    ///
+    /// # Errors:
+    ///
+    /// If a copy or fill fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let value = DeviceBox::new(&7u32).unwrap();
+    /// let mut buffer = DeviceBuffer::from_slice(&[0u32; 4]).unwrap();
+    /// buffer.fill_from_device_box(&value).unwrap();
+    /// let mut host_values = [0u32; 4];
+    /// buffer.copy_to(&mut host_values).unwrap();
+    /// assert_eq!([7, 7, 7, 7], host_values);
+    /// ```
+    pub fn fill_from_device_box(&mut self, value: &DeviceBox<T>) -> CudaResult<()> {
+        let size = size_of::<T>();
+        if size == 0 || self.capacity == 0 {
+            return Ok(());
+        }
+        unsafe {
+            cuda::cuMemcpyDtoD_v2(self.buf.as_raw_mut() as u64, value.device_ptr_raw(), size)
+                .to_result()?;
+        }
+        self.broadcast_from(0)
+    }
+
+    /// Overwrite individual elements of this buffer, given as `(index, value)` pairs.
+    ///
+    /// This is useful when only a few elements of a large buffer have changed, since it uploads
+    /// only those elements rather than the whole buffer. Consecutive pairs with consecutive
+    /// indices are coalesced into a single copy, so sorting the updates by index minimizes the
+    /// number of copies. If an index appears more than once, the last value for it wins.
+    ///
+    /// # Errors:
+    ///
+    /// If any index is out of bounds, returns InvalidValue without modifying the buffer. If a copy
+    /// fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut buffer = DeviceBuffer::from_slice(&[0u32; 6]).unwrap();
+    /// buffer.apply_updates(&[(1, 10), (2, 20), (5, 50)]).unwrap();
+    /// let mut host_values = [0u32; 6];
+    /// buffer.copy_to(&mut host_values).unwrap();
+    /// assert_eq!([0, 10, 20, 0, 0, 50], host_values);
+    /// ```
+    pub fn apply_updates(&mut self, updates: &[(usize, T)]) -> CudaResult<()> {
+        if updates.iter().any(|&(index, _)| index >= self.capacity) {
+            return Err(CudaError::InvalidValue);
+        }
+        let size = size_of::<T>();
+        if size == 0 {
+            return Ok(());
+        }
+
+        let base = self.buf.as_raw_mut() as u64;
+        let mut run = Vec::new();
+        let mut rest = updates;
+        while let Some(&(start, _)) = rest.first() {
+            let len = rest
+                .iter()
+                .enumerate()
+                .take_while(|&(offset, &(index, _))| index == start + offset)
+                .count();
+            // Gather the run's values into a contiguous array, since they are interleaved with
+            // indices in `updates`. The values are copied bitwise as `MaybeUninit<T>` because `T`
+            // isn't required to be `Clone`, and so that their padding is never read on the host.
+            run.clear();
+            run.extend(rest[..len].iter().map(|(_, value)| unsafe {
+                ptr::read(value as *const T as *const mem::MaybeUninit<T>)
+            }));
+            unsafe {
+                cuda::cuMemcpyHtoD_v2(
+                    base + (start * size) as u64,
+                    run.as_ptr() as *const c_void,
+                    len * size,
+                )
+                .to_result()?;
+            }
+            rest = &rest[len..];
+        }
+        Ok(())
+    }
+
+    /// Apply `f` to every element of this buffer on the host, returning a new device buffer
+    /// containing the results.
+    ///
+    /// This copies the contents of the buffer to the host, transforms each element and then copies
+    /// the results back to a newly-allocated device buffer. Since this costs two full transfers
+    /// across the PCI Express bus, it should only be used for small buffers or cheap transforms
+    /// which can't easily be expressed as a kernel.
+    ///
+    /// # Errors:
+    ///
+    /// If either copy or the allocation fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let buffer = DeviceBuffer::from_slice(&[1u32, 2, 3]).unwrap();
+    /// let doubled = buffer.map_into(|x| x as f32 * 2.0).unwrap();
+    /// let mut host_values = [0f32; 3];
+    /// doubled.copy_to(&mut host_values).unwrap();
+    /// assert_eq!([2.0f32, 4.0, 6.0], host_values);
+    /// ```
+    pub fn map_into<U: DeviceCopy, F: FnMut(T) -> U>(self, f: F) -> CudaResult<DeviceBuffer<U>> {
+        let mapped: Vec<U> = self.copy_to_vec()?.into_iter().map(f).collect();
+        DeviceBuffer::from_slice(&mapped)
+    }
 }
+impl<T: CheckedDeviceCopy> DeviceBuffer<T> {
+    /// Allocate a new device buffer of the same size as `slice`, initialized with a clone of
+    /// the data in `slice`, and check that the uploaded bytes match the layout declared by
+    /// [`CheckedDeviceCopy`](trait.CheckedDeviceCopy.html).
+    ///
+    /// The check only runs in debug builds with the `strict-device-copy` feature enabled, and
+    /// downloads the whole buffer again to compare it. Otherwise this is the same as
+    /// [`from_slice`](#method.from_slice).
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation or a copy fails, returns the error from CUDA.
+    ///
+    /// # Panics:
+    ///
+    /// When the check is enabled, panics if the size or alignment of `T` differs from the
+    /// declared layout, or if any uploaded byte which isn't padding differs from the value's
+    /// encoding.
+    pub fn from_slice_checked(slice: &[T]) -> CudaResult<Self> {
+        let buffer = DeviceBuffer::from_slice(slice)?;
+        #[cfg(all(feature = "strict-device-copy", debug_assertions))]
+        check_device_layout(slice, &buffer)?;
+        Ok(buffer)
+    }
+}
+/// Future which resolves once an upload started by
+/// [`DeviceBuffer::upload_async`](struct.DeviceBuffer.html#method.upload_async) has completed.
+///
+/// It owns the page-locked staging buffer used for the upload, and frees it once the upload is
+/// done.
+#[derive(Debug)]
+pub struct UploadCompletion<T: DeviceCopy> {
+    completion: StreamCompletion,
+    staging: Option<LockedBuffer<T>>,
+    // Recorded after the copy, so dropping the future early can wait for just the copy.
+    copied: Event,
+}
+impl<T: DeviceCopy> Future for UploadCompletion<T> {
+    type Output = CudaResult<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<CudaResult<()>> {
+        let this = self.get_mut();
+        match Pin::new(&mut this.completion).poll(cx) {
+            Poll::Ready(result) => {
+                this.staging = None;
+                Poll::Ready(result)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+impl<T: DeviceCopy> Drop for UploadCompletion<T> {
+    fn drop(&mut self) {
+        if self.staging.is_some() {
+            // The device may still be reading the staging buffer. If waiting fails, the copy has
+            // failed too, so the buffer is no longer in use either way.
+            let _ = self.copied.synchronize();
+        }
+    }
+}
+
+unsafe fn read_element<V>(value: &mut V, element: u64) -> CudaResult<()> {
+    cuda::cuMemcpyDtoH_v2(value as *mut V as *mut c_void, element, size_of::<V>()).to_result()
+}
+
 impl<T> Deref for DeviceBuffer<T> {
     type Target = DeviceSlice<T>;
 
@@ -221,6 +641,20 @@ impl<T> DerefMut for DeviceBuffer<T> {
         }
     }
 }
+impl<T> crate::private::Sealed for DeviceBuffer<T> {}
+impl<T> AnyDeviceBuffer for DeviceBuffer<T> {
+    fn len_bytes(&self) -> usize {
+        self.capacity * size_of::<T>()
+    }
+
+    fn device_ptr_raw(&self) -> CUdeviceptr {
+        self.buf.as_raw() as CUdeviceptr
+    }
+
+    fn element_size(&self) -> usize {
+        size_of::<T>()
+    }
+}
 impl<T> Drop for DeviceBuffer<T> {
     fn drop(&mut self) {
         if self.buf.is_null() {
@@ -241,7 +675,9 @@ impl<T> Drop for DeviceBuffer<T> {
 #[cfg(test)]
 mod test_device_buffer {
     use super::*;
-    use crate::memory::device::DeviceBox;
+    use crate::memory::device::{AsyncCopyDestination, DeviceBox};
+    use crate::memory::LockedBuffer;
+    use crate::stream::{Stream, StreamFlags};
 
     #[derive(Clone, Debug)]
     struct ZeroSizedType;
@@ -254,6 +690,18 @@ mod test_device_buffer {
         drop(buf);
     }
 
+    #[test]
+    fn test_zeroed() {
+        let _context = crate::quick_init().unwrap();
+        let buffer = DeviceBuffer::<u8>::zeroed(1 << 20).unwrap();
+        let mut host = vec![0xFFu8; 1 << 20];
+        buffer.copy_to(&mut host[..]).unwrap();
+        assert!(host.iter().all(|&b| b == 0));
+
+        let empty = DeviceBuffer::<u64>::zeroed(0).unwrap();
+        assert!(empty.is_empty());
+    }
+
     #[test]
     fn test_copy_to_from_device() {
         let _context = crate::quick_init().unwrap();
@@ -275,6 +723,31 @@ mod test_device_buffer {
         assert_eq!(start[0..2], end);
     }
 
+    #[test]
+    fn test_index_range_forms() {
+        let _context = crate::quick_init().unwrap();
+        let mut buf = DeviceBuffer::from_slice(&[0u64, 1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(vec![1u64, 2], buf[1..3].copy_to_vec().unwrap());
+        assert_eq!(vec![1u64, 2, 3], buf[1..=3].copy_to_vec().unwrap());
+        assert_eq!(vec![0u64, 1], buf[..2].copy_to_vec().unwrap());
+        assert_eq!(vec![0u64, 1, 2], buf[..=2].copy_to_vec().unwrap());
+        assert_eq!(vec![4u64, 5], buf[4..].copy_to_vec().unwrap());
+        assert_eq!(6, buf[..].len());
+        assert!(buf[6..].is_empty());
+
+        buf[4..=5].copy_from(&[40u64, 50]).unwrap();
+        buf[..=0].copy_from(&[10u64]).unwrap();
+        assert_eq!(vec![10u64, 1, 2, 3, 40, 50], buf.copy_to_vec().unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let _context = crate::quick_init().unwrap();
+        let buf = DeviceBuffer::from_slice(&[0u64, 1, 2, 3, 4, 5]).unwrap();
+        let _ = &buf[4..=6];
+    }
+
     #[test]
     #[should_panic]
     fn test_copy_to_d2h_wrong_size() {
@@ -324,6 +797,390 @@ mod test_device_buffer {
         let _ = buf.copy_from(&start);
     }
 
+    #[test]
+    fn test_map_into() {
+        let _context = crate::quick_init().unwrap();
+        let buf = DeviceBuffer::from_slice(&[0u32, 1, 2, 3]).unwrap();
+        let mapped = buf.map_into(|x| x as f32).unwrap();
+        let mut end = [5.0f32; 4];
+        mapped.copy_to(&mut end).unwrap();
+        assert_eq!([0.0f32, 1.0, 2.0, 3.0], end);
+    }
+
+    #[test]
+    fn test_to_vec_swapped() {
+        let _context = crate::quick_init().unwrap();
+        let start = [0x0102_0304u32, 0xdead_beef, 0, 1];
+        let buf = DeviceBuffer::from_slice(&start).unwrap();
+        let swapped = buf.to_vec_swapped().unwrap();
+        let expected: Vec<u32> = start.iter().map(|x| x.swap_bytes()).collect();
+        assert_eq!(expected, swapped);
+    }
+
+    #[test]
+    fn test_iter_copied() {
+        let _context = crate::quick_init().unwrap();
+        let values: Vec<u64> = (1..=10_000).collect();
+        let buf = DeviceBuffer::from_slice(&values).unwrap();
+        let sum = buf.iter_copied(256).sum::<CudaResult<u64>>().unwrap();
+        assert_eq!(10_000 * 10_001 / 2, sum);
+
+        let mut iter = buf[..3].iter_copied(2);
+        assert_eq!(Some(Ok(1)), iter.next());
+        assert_eq!(Some(Ok(2)), iter.next());
+        assert_eq!(Some(Ok(3)), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_any_device_buffer() {
+        use crate::memory::device::AnyDeviceBuffer;
+
+        let _context = crate::quick_init().unwrap();
+        let floats = DeviceBuffer::from_slice(&[0.0f32; 10]).unwrap();
+        let pointer = floats.as_ptr() as CUdeviceptr;
+        let buffers: Vec<Box<dyn AnyDeviceBuffer>> = vec![
+            Box::new(floats),
+            Box::new(DeviceBuffer::from_slice(&[0u8; 3]).unwrap()),
+            Box::new(DeviceBox::new(&0u64).unwrap()),
+        ];
+        let lengths: Vec<usize> = buffers.iter().map(|b| b.len_bytes()).collect();
+        assert_eq!(vec![40, 3, 8], lengths);
+        let sizes: Vec<usize> = buffers.iter().map(|b| b.element_size()).collect();
+        assert_eq!(vec![4, 1, 8], sizes);
+        assert_eq!(pointer, buffers[0].device_ptr_raw());
+    }
+
+    #[test]
+    fn test_write_to() {
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        // Large enough to be written in several chunks, with a partial chunk at the end.
+        let values: Vec<u32> = (0..600_000).collect();
+        let buf = DeviceBuffer::from_slice(&values).unwrap();
+        let mut bytes = Vec::new();
+        buf.write_to(&mut bytes, &stream).unwrap();
+
+        let expected: Vec<u8> = values
+            .iter()
+            .flat_map(|x| x.to_ne_bytes().to_vec())
+            .collect();
+        assert_eq!(expected, bytes);
+    }
+
+    #[test]
+    fn test_write_to_failing_writer() {
+        use std::io;
+
+        struct FailingWriter;
+        impl io::Write for FailingWriter {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk full"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let buf = DeviceBuffer::from_slice(&vec![0u32; 600_000]).unwrap();
+        let error = buf.write_to(FailingWriter, &stream).unwrap_err();
+        assert_eq!("disk full", error.to_string());
+        // The download of the second chunk was finished before the staging buffers were freed.
+        assert!(stream.query().unwrap());
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Tagged {
+        tag: u8,
+        value: u32,
+    }
+    unsafe impl DeviceCopy for Tagged {}
+    impl CheckedDeviceCopy for Tagged {
+        const DEVICE_SIZE: usize = 8;
+        const DEVICE_ALIGN: usize = 4;
+
+        fn encode_device(&self, bytes: &mut [u8]) {
+            bytes[0] = self.tag;
+            bytes[4..8].copy_from_slice(&self.value.to_ne_bytes());
+        }
+    }
+
+    // Declared with its fields in the opposite order to the device code.
+    #[cfg(all(feature = "strict-device-copy", debug_assertions))]
+    #[derive(Clone, Copy, Debug)]
+    #[repr(C)]
+    struct Swapped {
+        value: u32,
+        tag: u8,
+    }
+    #[cfg(all(feature = "strict-device-copy", debug_assertions))]
+    unsafe impl DeviceCopy for Swapped {}
+    #[cfg(all(feature = "strict-device-copy", debug_assertions))]
+    impl CheckedDeviceCopy for Swapped {
+        const DEVICE_SIZE: usize = 8;
+        const DEVICE_ALIGN: usize = 4;
+
+        fn encode_device(&self, bytes: &mut [u8]) {
+            bytes[0] = self.tag;
+            bytes[4..8].copy_from_slice(&self.value.to_ne_bytes());
+        }
+    }
+
+    #[test]
+    fn test_from_slice_checked() {
+        let _context = crate::quick_init().unwrap();
+        let values = [Tagged { tag: 1, value: 10 }, Tagged { tag: 2, value: 20 }];
+        let buf = DeviceBuffer::from_slice_checked(&values).unwrap();
+        assert_eq!(values.to_vec(), buf.copy_to_vec().unwrap());
+    }
+
+    #[cfg(all(feature = "strict-device-copy", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "DeviceCopy layout mismatch")]
+    fn test_from_slice_checked_detects_mismatch() {
+        let _context = crate::quick_init().unwrap();
+        let _ = DeviceBuffer::from_slice_checked(&[Swapped { value: 10, tag: 1 }]);
+    }
+
+    #[test]
+    fn test_fill_from_device_box() {
+        let _context = crate::quick_init().unwrap();
+        let value = DeviceBox::new(&0xDEAD_BEEFu32).unwrap();
+        let mut buf = DeviceBuffer::from_slice(&[0u32; 1000]).unwrap();
+        buf.fill_from_device_box(&value).unwrap();
+        assert_eq!(vec![0xDEAD_BEEFu32; 1000], buf.copy_to_vec().unwrap());
+
+        // Larger elements are filled without a host round trip.
+        let value = DeviceBox::new(&[1u64, 2, 3]).unwrap();
+        let mut buf = DeviceBuffer::from_slice(&[[0u64; 3]; 37]).unwrap();
+        buf.fill_from_device_box(&value).unwrap();
+        assert_eq!(vec![[1u64, 2, 3]; 37], buf.copy_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        let _context = crate::quick_init().unwrap();
+        let values = [0x0403_0201u32, 0x0807_0605, 0x0C0B_0A09];
+        let mut buf = DeviceBuffer::from_slice(&values).unwrap();
+        assert_eq!(4 * buf.len(), buf.as_bytes().len());
+        let bytes = buf.as_bytes().copy_to_vec().unwrap();
+        let expected: Vec<u8> = values
+            .iter()
+            .flat_map(|x| x.to_ne_bytes().to_vec())
+            .collect();
+        assert_eq!(expected, bytes);
+
+        unsafe {
+            buf.as_bytes_mut()[4..8].copy_from(&[0u8; 4]).unwrap();
+        }
+        assert_eq!(vec![values[0], 0, values[2]], buf.copy_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_copy_to_vec() {
+        let _context = crate::quick_init().unwrap();
+        let start = [0u64, 1, 2, 3, 4, 5];
+        let buf = DeviceBuffer::from_slice(&start).unwrap();
+        assert_eq!(&start[..], &buf.copy_to_vec().unwrap()[..]);
+        assert_eq!(&start[2..4], &buf[2..4].copy_to_vec().unwrap()[..]);
+    }
+
+    #[test]
+    fn test_partition_indices_host() {
+        let _context = crate::quick_init().unwrap();
+        let buf = DeviceBuffer::from_slice(&[4u8, 7, 0, 3, 3, 8, 1]).unwrap();
+        let (even, odd) = buf.partition_indices_host(|x| x % 2 == 0).unwrap();
+        assert_eq!(vec![0, 2, 5], even);
+        assert_eq!(vec![1, 3, 4, 6], odd);
+
+        let empty = DeviceBuffer::<u8>::from_slice(&[]).unwrap();
+        let (even, odd) = empty.partition_indices_host(|x| x % 2 == 0).unwrap();
+        assert!(even.is_empty());
+        assert!(odd.is_empty());
+    }
+
+    #[test]
+    fn test_async_copy_to_locked() {
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let start = [0u64, 1, 2, 3, 4, 5];
+        let buf = DeviceBuffer::from_slice(&start).unwrap();
+        let mut locked = LockedBuffer::new(&0u64, start.len()).unwrap();
+        unsafe {
+            buf.async_copy_to(&mut locked, &stream).unwrap();
+        }
+        stream.synchronize().unwrap();
+        assert_eq!(&start[..], locked.as_slice());
+    }
+
+    #[test]
+    fn test_async_copy_from_locked() {
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let start = LockedBuffer::from_slice(&[0u64, 1, 2, 3, 4, 5]).unwrap();
+        let mut buf = DeviceBuffer::from_slice(&[0u64; 6]).unwrap();
+        let mut copy = DeviceBuffer::from_slice(&[0u64; 6]).unwrap();
+        unsafe {
+            buf.async_copy_from(&start, &stream).unwrap();
+            buf.async_copy_to(&mut copy, &stream).unwrap();
+        }
+        stream.synchronize().unwrap();
+        assert_eq!(start.as_slice(), &copy.copy_to_vec().unwrap()[..]);
+    }
+
+    #[test]
+    fn test_async_copies_overlap_on_two_streams() {
+        let _context = crate::quick_init().unwrap();
+        let upload_stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let download_stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+
+        let source: Vec<u32> = (0..1 << 16).collect();
+        let upload = LockedBuffer::from_slice(&source).unwrap();
+        let mut uploaded = DeviceBuffer::from_slice(&[0u32; 1 << 16]).unwrap();
+        let downloading = DeviceBuffer::from_slice(&source).unwrap();
+        let mut download = LockedBuffer::new(&0u32, 1 << 16).unwrap();
+        unsafe {
+            uploaded.async_copy_from(&upload, &upload_stream).unwrap();
+            downloading
+                .async_copy_to(&mut download, &download_stream)
+                .unwrap();
+        }
+        upload_stream.synchronize().unwrap();
+        download_stream.synchronize().unwrap();
+
+        assert_eq!(source, uploaded.copy_to_vec().unwrap());
+        assert_eq!(&source[..], download.as_slice());
+    }
+
+    #[test]
+    fn test_upload_async_then_launch() {
+        use crate::function::TypedFunction;
+        use crate::module::Module;
+        use crate::test_util::block_on;
+        use std::ffi::CString;
+
+        let _context = crate::quick_init().unwrap();
+        let ptx = CString::new(include_str!("../../../resources/add.ptx")).unwrap();
+        let module = Module::load_from_string(&ptx).unwrap();
+        let function = module.get_function(&CString::new("sum").unwrap()).unwrap();
+        let sum = TypedFunction::new(function);
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+
+        let (mut x, x_uploaded) = DeviceBuffer::upload_async(&[1.0f32; 128], &stream).unwrap();
+        let (mut y, y_uploaded) = DeviceBuffer::upload_async(&[2.0f32; 128], &stream).unwrap();
+        let mut out = DeviceBuffer::from_slice(&[0.0f32; 128]).unwrap();
+        drop(x_uploaded);
+        unsafe {
+            sum.launch(
+                &stream,
+                1,
+                128,
+                0,
+                (
+                    x.as_device_ptr(),
+                    y.as_device_ptr(),
+                    out.as_device_ptr(),
+                    out.len() as i32,
+                ),
+            )
+            .unwrap();
+        }
+
+        block_on(y_uploaded).unwrap();
+        stream.synchronize().unwrap();
+        assert_eq!(vec![3.0f32; 128], out.copy_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_clone_from_device() {
+        let _context = crate::quick_init().unwrap();
+        let mut dst = DeviceBuffer::from_slice(&[0u64; 4]).unwrap();
+        let ptr = dst.as_device_ptr();
+        for i in 0..3u64 {
+            let src = DeviceBuffer::from_slice(&[i, i + 1, i + 2, i + 3]).unwrap();
+            dst.clone_from_device(&src).unwrap();
+            assert_eq!(vec![i, i + 1, i + 2, i + 3], dst.copy_to_vec().unwrap());
+        }
+        assert_eq!(ptr, dst.as_device_ptr());
+
+        let short = DeviceBuffer::from_slice(&[0u64; 3]).unwrap();
+        assert_eq!(Err(CudaError::InvalidValue), dst.clone_from_device(&short));
+    }
+
+    #[test]
+    fn test_apply_updates() {
+        let _context = crate::quick_init().unwrap();
+        let mut buffer = DeviceBuffer::from_slice(&[0u64; 10]).unwrap();
+        buffer
+            .apply_updates(&[(7, 70), (2, 20), (3, 30), (4, 40), (0, 1), (7, 77)])
+            .unwrap();
+        assert_eq!(
+            vec![1u64, 0, 20, 30, 40, 0, 0, 77, 0, 0],
+            buffer.copy_to_vec().unwrap()
+        );
+
+        assert_eq!(
+            Err(CudaError::InvalidValue),
+            buffer.apply_updates(&[(0, 5), (10, 5)])
+        );
+        assert_eq!(1, buffer.copy_to_vec().unwrap()[0]);
+    }
+
+    #[test]
+    fn test_apply_updates_padded() {
+        let _context = crate::quick_init().unwrap();
+        let zero = Tagged { tag: 0, value: 0 };
+        let mut buffer = DeviceBuffer::from_slice(&[zero; 4]).unwrap();
+        let one = Tagged { tag: 1, value: 10 };
+        let two = Tagged { tag: 2, value: 20 };
+        buffer.apply_updates(&[(1, one), (2, two)]).unwrap();
+        assert_eq!(vec![zero, one, two, zero], buffer.copy_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_broadcast_from() {
+        let _context = crate::quick_init().unwrap();
+        let mut buffer = DeviceBuffer::from_slice(&[1u32, 2, 3, 4, 5]).unwrap();
+        buffer.broadcast_from(2).unwrap();
+        assert_eq!(vec![3u32; 5], buffer.copy_to_vec().unwrap());
+
+        // Elements which aren't one, two or four bytes are filled with device copies.
+        let values: Vec<[u8; 3]> = (0..7u8).map(|i| [i, i + 1, i + 2]).collect();
+        let mut buffer = DeviceBuffer::from_slice(&values).unwrap();
+        buffer.broadcast_from(2).unwrap();
+        assert_eq!(vec![[2u8, 3, 4]; 7], buffer.copy_to_vec().unwrap());
+
+        assert_eq!(Err(CudaError::InvalidValue), buffer.broadcast_from(7));
+    }
+
+    #[test]
+    fn test_element_ptr() {
+        let _context = crate::quick_init().unwrap();
+        let buf = DeviceBuffer::from_slice(&[0u64, 1, 2, 3, 4, 5]).unwrap();
+        let base = buf.element_ptr(0).unwrap().as_raw() as usize;
+        let third = buf.element_ptr(3).unwrap().as_raw() as usize;
+        assert_eq!(base + 3 * size_of::<u64>(), third);
+        assert_eq!(Err(CudaError::InvalidValue), buf.element_ptr(6));
+    }
+
+    #[test]
+    fn test_contains_ptr() {
+        let _context = crate::quick_init().unwrap();
+        let buf = DeviceBuffer::from_slice(&[0u64, 1, 2, 3, 4, 5]).unwrap();
+        let first = buf.element_ptr(0).unwrap();
+        let last = buf.element_ptr(5).unwrap();
+        assert!(buf.contains_ptr(first));
+        assert!(buf.contains_ptr(last));
+        assert!(!buf.contains_ptr(last.wrapping_add(1)));
+        assert!(!buf.contains_ptr(first.wrapping_sub(1)));
+
+        let other = DeviceBuffer::from_slice(&[0u64; 6]).unwrap();
+        assert!(!buf.contains_ptr(other.element_ptr(0).unwrap()));
+    }
+
     #[test]
     fn test_can_create_uninitialized_non_devicecopy_buffers() {
         let _context = crate::quick_init().unwrap();
@@ -333,4 +1190,71 @@ mod test_device_buffer {
             let _slice = &buffer[0..5];
         }
     }
+
+    #[test]
+    fn test_copy_peer() {
+        use crate::context::{Context, ContextFlags, CurrentContext};
+        use crate::device::Device;
+
+        crate::init(crate::CudaFlags::empty()).unwrap();
+        if Device::num_devices().unwrap() < 2 {
+            return;
+        }
+        let device0 = Device::get_device(0).unwrap();
+        let device1 = Device::get_device(1).unwrap();
+        if !device0.can_access_peer(device1).unwrap() {
+            return;
+        }
+        let context0 = Context::create_and_push(ContextFlags::SCHED_AUTO, device0).unwrap();
+        let context1 = Context::create_and_push(ContextFlags::SCHED_AUTO, device1).unwrap();
+        context1.enable_peer_access(&context0).unwrap();
+
+        CurrentContext::set_current(&context0).unwrap();
+        let src = DeviceBuffer::from_slice(&[1u64, 2, 3, 4]).unwrap();
+        CurrentContext::set_current(&context1).unwrap();
+        let mut dst = DeviceBuffer::from_slice(&[0u64; 4]).unwrap();
+        dst.copy_peer(&context1, &src, &context0).unwrap();
+        assert_eq!(vec![1u64, 2, 3, 4], dst.copy_to_vec().unwrap());
+
+        let mut short = DeviceBuffer::from_slice(&[0u64; 3]).unwrap();
+        assert_eq!(
+            Err(CudaError::InvalidValue),
+            short.copy_peer(&context1, &src, &context0)
+        );
+    }
+
+    #[test]
+    fn test_copy_peer_async() {
+        use crate::context::{Context, ContextFlags, CurrentContext};
+        use crate::device::Device;
+        use crate::memory::copy_peer_async;
+
+        crate::init(crate::CudaFlags::empty()).unwrap();
+        if Device::num_devices().unwrap() < 2 {
+            return;
+        }
+        let device0 = Device::get_device(0).unwrap();
+        let device1 = Device::get_device(1).unwrap();
+        if !device0.can_access_peer(device1).unwrap() {
+            return;
+        }
+        let context0 = Context::create_and_push(ContextFlags::SCHED_AUTO, device0).unwrap();
+        let context1 = Context::create_and_push(ContextFlags::SCHED_AUTO, device1).unwrap();
+        context1.enable_peer_access(&context0).unwrap();
+
+        CurrentContext::set_current(&context0).unwrap();
+        let src = DeviceBuffer::from_slice(&[1u64, 2, 3, 4]).unwrap();
+        CurrentContext::set_current(&context1).unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let mut dst = DeviceBuffer::from_slice(&[0u64; 4]).unwrap();
+        unsafe {
+            copy_peer_async(&mut dst, &context1, &src, &context0, &stream).unwrap();
+        }
+        stream.synchronize().unwrap();
+        assert_eq!(vec![1u64, 2, 3, 4], dst.copy_to_vec().unwrap());
+
+        let mut short = DeviceBuffer::from_slice(&[0u64; 3]).unwrap();
+        let result = unsafe { copy_peer_async(&mut short, &context1, &src, &context0, &stream) };
+        assert_eq!(Err(CudaError::InvalidValue), result);
+    }
 }
@@ -0,0 +1,134 @@
+use crate::error::{CudaResult, ToResult};
+use crate::memory::device::{CopyDestination, DeviceBox};
+use crate::memory::DevicePointer;
+use cuda_sys::cuda;
+
+/// A `u32` counter in device memory, intended to be incremented atomically by kernels.
+///
+/// `DeviceCounter` wraps a [`DeviceBox<u32>`](struct.DeviceBox.html) and provides helpers for the
+/// common pattern of reading the counter and resetting it to zero between kernel launches.
+#[derive(Debug)]
+pub struct DeviceCounter {
+    inner: DeviceBox<u32>,
+}
+impl DeviceCounter {
+    /// Allocate a new counter in device memory, initialized to zero.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let counter = DeviceCounter::new().unwrap();
+    /// assert_eq!(0, counter.read().unwrap());
+    /// ```
+    pub fn new() -> CudaResult<Self> {
+        Ok(DeviceCounter {
+            inner: DeviceBox::zeroed()?,
+        })
+    }
+
+    /// Copy the current value of the counter to the host.
+    ///
+    /// This is a synchronous copy, so it waits for any work writing to the counter to finish.
+    ///
+    /// # Errors:
+    ///
+    /// If the copy fails, returns the error from CUDA.
+    pub fn read(&self) -> CudaResult<u32> {
+        let mut value = 0u32;
+        self.inner.copy_to(&mut value)?;
+        Ok(value)
+    }
+
+    /// Reset the counter to zero.
+    ///
+    /// # Errors:
+    ///
+    /// If the memset fails, returns the error from CUDA.
+    pub fn reset(&mut self) -> CudaResult<()> {
+        unsafe {
+            cuda::cuMemsetD32_v2(self.inner.as_device_ptr().as_raw_mut() as u64, 0, 1).to_result()
+        }
+    }
+
+    /// Copy the current value of the counter to the host, then reset it to zero.
+    ///
+    /// # Errors:
+    ///
+    /// If either the copy or the memset fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut counter = DeviceCounter::new().unwrap();
+    ///
+    /// // ... launch a kernel which increments the counter
+    ///
+    /// let count = counter.read_and_reset().unwrap();
+    /// assert_eq!(0, counter.read().unwrap());
+    /// ```
+    pub fn read_and_reset(&mut self) -> CudaResult<u32> {
+        let value = self.read()?;
+        self.reset()?;
+        Ok(value)
+    }
+
+    /// Returns a device pointer to the counter, for passing to a kernel launch.
+    pub fn as_device_ptr(&mut self) -> DevicePointer<u32> {
+        self.inner.as_device_ptr()
+    }
+}
+
+#[cfg(test)]
+mod test_device_counter {
+    use super::*;
+    use crate::function::TypedFunction;
+    use crate::module::Module;
+    use crate::stream::{Stream, StreamFlags};
+    use std::ffi::CString;
+
+    #[test]
+    fn test_new() {
+        let _context = crate::quick_init().unwrap();
+        let counter = DeviceCounter::new().unwrap();
+        assert_eq!(0, counter.read().unwrap());
+    }
+
+    #[test]
+    fn test_read_and_reset() {
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let ptx = CString::new(include_str!("../../../resources/add.ptx")).unwrap();
+        let module = Module::load_from_string(&ptx).unwrap();
+        let function = module
+            .get_function(&CString::new("count").unwrap())
+            .unwrap();
+        let count = TypedFunction::new(function);
+        let mut counter = DeviceCounter::new().unwrap();
+
+        unsafe {
+            count
+                .launch(&stream, 4, 64, 0, (counter.as_device_ptr(), 200i32))
+                .unwrap();
+        }
+        stream.synchronize().unwrap();
+        assert_eq!(200, counter.read_and_reset().unwrap());
+        assert_eq!(0, counter.read().unwrap());
+
+        // The next launch counts up from zero again.
+        unsafe {
+            count
+                .launch(&stream, 1, 64, 0, (counter.as_device_ptr(), 10i32))
+                .unwrap();
+        }
+        stream.synchronize().unwrap();
+        assert_eq!(10, counter.read_and_reset().unwrap());
+    }
+}
@@ -0,0 +1,158 @@
+use crate::error::{CudaError, CudaResult};
+use crate::memory::device::{CopyDestination, DeviceBuffer, DevicePitchedBuffer, DeviceSlice};
+use crate::memory::DeviceCopy;
+use ndarray::{Array, Array2, ArrayView, ArrayView2, Dimension, ShapeBuilder};
+
+impl<T: DeviceCopy> DeviceBuffer<T> {
+    /// Allocate a new device buffer holding the elements of `array`, in row-major order.
+    ///
+    /// Only available with the `ndarray` feature.
+    ///
+    /// # Errors:
+    ///
+    /// If `array` is not in standard (row-major and contiguous) layout, returns InvalidValue.
+    /// Use `as_standard_layout` to copy non-contiguous views into that layout first. If the
+    /// allocation or copy fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use ndarray::arr2;
+    /// use rustacuda::memory::*;
+    /// let array = arr2(&[[1.0f32, 2.0], [3.0, 4.0]]);
+    /// let buffer = DeviceBuffer::from_array(array.view()).unwrap();
+    /// assert_eq!(array, buffer.copy_to_array((2, 2)).unwrap());
+    /// ```
+    pub fn from_array<D: Dimension>(array: ArrayView<T, D>) -> CudaResult<Self> {
+        let slice = array.to_slice().ok_or(CudaError::InvalidValue)?;
+        let mut buffer = unsafe { DeviceBuffer::uninitialized(slice.len())? };
+        buffer.copy_from(slice)?;
+        Ok(buffer)
+    }
+}
+
+impl<T: DeviceCopy> DeviceSlice<T> {
+    /// Copy the contents of this slice into a newly-allocated array of the given shape, in
+    /// row-major order.
+    ///
+    /// Only available with the `ndarray` feature.
+    ///
+    /// # Errors:
+    ///
+    /// If the number of elements in `shape` is not the length of this slice, returns
+    /// InvalidValue. If the copy fails, returns the error from CUDA.
+    pub fn copy_to_array<Sh>(&self, shape: Sh) -> CudaResult<Array<T, Sh::Dim>>
+    where
+        Sh: ShapeBuilder,
+    {
+        let shape = shape.into_shape();
+        if shape.size() != self.len() {
+            return Err(CudaError::InvalidValue);
+        }
+        Array::from_shape_vec(shape, self.copy_to_vec()?).map_err(|_| CudaError::InvalidValue)
+    }
+}
+
+impl<T: DeviceCopy> DevicePitchedBuffer<T> {
+    /// Copy a two-dimensional array into this buffer. Row `y` of the array becomes row `y` of
+    /// the buffer.
+    ///
+    /// Only available with the `ndarray` feature.
+    ///
+    /// # Errors:
+    ///
+    /// If the array's shape is not `(height, width)`, or it is not in standard layout, returns
+    /// InvalidValue. If the copy fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use ndarray::arr2;
+    /// use rustacuda::memory::*;
+    /// let array = arr2(&[[1u32, 2, 3], [4, 5, 6]]);
+    /// let mut buffer = unsafe { DevicePitchedBuffer::uninitialized(3, 2).unwrap() };
+    /// buffer.copy_from_array(array.view()).unwrap();
+    /// assert_eq!(array, buffer.copy_to_array().unwrap());
+    /// ```
+    pub fn copy_from_array(&mut self, array: ArrayView2<T>) -> CudaResult<()> {
+        if array.dim() != (self.height(), self.width()) {
+            return Err(CudaError::InvalidValue);
+        }
+        let slice = array.to_slice().ok_or(CudaError::InvalidValue)?;
+        self.copy_from_2d(slice)
+    }
+
+    /// Copy the contents of this buffer into a newly-allocated array of shape
+    /// `(height, width)`, dropping the padding at the end of each row.
+    ///
+    /// Only available with the `ndarray` feature.
+    ///
+    /// # Errors:
+    ///
+    /// If the copy fails, returns the error from CUDA.
+    pub fn copy_to_array(&self) -> CudaResult<Array2<T>> {
+        let len = self.width() * self.height();
+        let mut vec = Vec::with_capacity(len);
+        unsafe {
+            self.copy_to_2d_ptr(vec.as_mut_ptr())?;
+            vec.set_len(len);
+        }
+        Array2::from_shape_vec((self.height(), self.width()), vec)
+            .map_err(|_| CudaError::InvalidValue)
+    }
+}
+
+#[cfg(test)]
+mod test_device_ndarray {
+    use super::*;
+    use ndarray::{arr2, s};
+
+    #[test]
+    fn test_round_trip_3x4() {
+        let _context = crate::quick_init().unwrap();
+        let array = Array2::from_shape_fn((3, 4), |(y, x)| (y * 4 + x) as f32);
+        let buffer = DeviceBuffer::from_array(array.view()).unwrap();
+        let result = buffer.copy_to_array((3, 4)).unwrap();
+        for (expected, actual) in array.iter().zip(result.iter()) {
+            assert_eq!(expected, actual);
+        }
+        assert_eq!(
+            Err(CudaError::InvalidValue),
+            buffer.copy_to_array((4, 4)).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_non_standard_layout_is_rejected() {
+        let _context = crate::quick_init().unwrap();
+        let array = arr2(&[[1u32, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            Err(CudaError::InvalidValue),
+            DeviceBuffer::from_array(array.t()).map(|_| ())
+        );
+        let transposed = array.t().as_standard_layout().into_owned();
+        let buffer = DeviceBuffer::from_array(transposed.view()).unwrap();
+        assert_eq!(vec![1, 4, 2, 5, 3, 6], buffer.copy_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_pitched_round_trip() {
+        let _context = crate::quick_init().unwrap();
+        let array = Array2::from_shape_fn((3, 4), |(y, x)| (y * 4 + x) as u8);
+        let mut buffer = unsafe { DevicePitchedBuffer::uninitialized(4, 3).unwrap() };
+        buffer.copy_from_array(array.view()).unwrap();
+        assert_eq!(array, buffer.copy_to_array().unwrap());
+
+        // Same shape as the buffer, but every other column of a wider array, so not contiguous.
+        let wide = Array2::from_shape_fn((3, 8), |(y, x)| (y * 8 + x) as u8);
+        let strided = wide.slice(s![.., ..;2]);
+        assert_eq!(buffer.height(), strided.nrows());
+        assert_eq!(buffer.width(), strided.ncols());
+        assert_eq!(
+            Err(CudaError::InvalidValue),
+            buffer.copy_from_array(strided)
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use crate::error::{CudaError, CudaResult, DropResult, ToResult};
+use crate::memory::device::AnyDeviceBuffer;
+use crate::memory::malloc::{cuda_free, track_alloc};
+use crate::memory::memcpy_2d::empty_copy_2d;
+use crate::memory::DeviceCopy;
+use crate::memory::DevicePointer;
+use cuda_sys::cuda::{self, CUdeviceptr, CUmemorytype, CUDA_MEMCPY2D};
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+
+/// Fixed-size, two-dimensional device-side buffer with padded rows.
+///
+/// The driver may pad each row of a pitched allocation so that every row starts at a suitably
+/// aligned address, which allows kernels to access the rows with coalesced memory transactions.
+/// The distance between the start of two consecutive rows, in bytes, is the buffer's
+/// [`pitch`](#method.pitch). Kernels must use the pitch rather than the width to find the start
+/// of a row; element `(x, y)` is at byte offset `y * pitch + x * mem::size_of::<T>()`.
+#[derive(Debug)]
+pub struct DevicePitchedBuffer<T: DeviceCopy> {
+    buf: DevicePointer<T>,
+    width: usize,
+    height: usize,
+    pitch: usize,
+}
+impl<T: DeviceCopy> DevicePitchedBuffer<T> {
+    /// Allocate a new pitched buffer of `height` rows of `width` `T`'s each, but without
+    /// initializing the contents.
+    ///
+    /// If `width` or `height` is zero, or `T` is zero-sized, no memory is allocated.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA. If `width` is large enough that
+    /// `width * mem::size_of::<T>()` overflows usize, then returns InvalidMemoryAllocation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the contents of the buffer are initialized before reading from
+    /// the buffer.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut buffer = unsafe { DevicePitchedBuffer::uninitialized(3, 2).unwrap() };
+    /// buffer.copy_from_2d(&[0u32, 1, 2, 3, 4, 5]).unwrap();
+    /// assert!(buffer.pitch() >= 3 * 4);
+    /// ```
+    pub unsafe fn uninitialized(width: usize, height: usize) -> CudaResult<Self> {
+        let width_bytes = width
+            .checked_mul(size_of::<T>())
+            .ok_or(CudaError::InvalidMemoryAllocation)?;
+
+        if width_bytes == 0 || height == 0 {
+            return Ok(DevicePitchedBuffer {
+                buf: DevicePointer::wrap(ptr::NonNull::dangling().as_ptr()),
+                width,
+                height,
+                pitch: width_bytes,
+            });
+        }
+
+        // The driver only accepts 4, 8 or 16 as the size of the accesses kernels will make.
+        let element_size = match size_of::<T>() {
+            size @ 4 | size @ 8 | size @ 16 => size,
+            _ => 16,
+        };
+        let mut ptr: CUdeviceptr = 0;
+        let mut pitch = 0usize;
+        cuda::cuMemAllocPitch_v2(
+            &mut ptr as *mut CUdeviceptr,
+            &mut pitch as *mut usize,
+            width_bytes,
+            height,
+            element_size as u32,
+        )
+        .to_result()?;
+        track_alloc(ptr as usize);
+        Ok(DevicePitchedBuffer {
+            buf: DevicePointer::wrap(ptr as *mut T),
+            width,
+            height,
+            pitch,
+        })
+    }
+
+    /// Returns the number of elements in each row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the distance in bytes between the starts of two consecutive rows.
+    ///
+    /// This is at least `width * mem::size_of::<T>()`, and should be passed to kernels along with
+    /// the pointer to the buffer.
+    pub fn pitch(&self) -> usize {
+        self.pitch
+    }
+
+    /// Returns a `DevicePointer<T>` to the first element of the first row.
+    ///
+    /// The caller must ensure that the buffer outlives the returned pointer.
+    pub fn as_device_ptr(&mut self) -> DevicePointer<T> {
+        self.buf
+    }
+
+    /// Copy `src`, a tightly-packed row-major array of `height` rows of `width` elements, into
+    /// this buffer.
+    ///
+    /// # Errors:
+    ///
+    /// If a CUDA error occurs, returns the error.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `src.len()` is not `width * height`.
+    pub fn copy_from_2d(&mut self, src: &[T]) -> CudaResult<()> {
+        assert!(
+            src.len() == self.width * self.height,
+            "source slice does not have width * height elements"
+        );
+        let width_bytes = self.width * size_of::<T>();
+        if width_bytes == 0 || self.height == 0 {
+            return Ok(());
+        }
+        let copy = CUDA_MEMCPY2D {
+            srcMemoryType: CUmemorytype::CU_MEMORYTYPE_HOST,
+            srcHost: src.as_ptr() as *const c_void,
+            srcPitch: width_bytes,
+            dstMemoryType: CUmemorytype::CU_MEMORYTYPE_DEVICE,
+            dstDevice: self.buf.as_raw() as CUdeviceptr,
+            dstPitch: self.pitch,
+            WidthInBytes: width_bytes,
+            Height: self.height,
+            ..empty_copy_2d()
+        };
+        unsafe { cuda::cuMemcpy2D_v2(&copy as *const CUDA_MEMCPY2D).to_result() }
+    }
+
+    /// Copy the contents of this buffer into `dst`, a tightly-packed row-major array of `height`
+    /// rows of `width` elements.
+    ///
+    /// # Errors:
+    ///
+    /// If a CUDA error occurs, returns the error.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `dst.len()` is not `width * height`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut buffer = unsafe { DevicePitchedBuffer::uninitialized(2, 2).unwrap() };
+    /// buffer.copy_from_2d(&[1u64, 2, 3, 4]).unwrap();
+    /// let mut host = [0u64; 4];
+    /// buffer.copy_to_2d(&mut host).unwrap();
+    /// assert_eq!([1, 2, 3, 4], host);
+    /// ```
+    pub fn copy_to_2d(&self, dst: &mut [T]) -> CudaResult<()> {
+        assert!(
+            dst.len() == self.width * self.height,
+            "destination slice does not have width * height elements"
+        );
+        unsafe { self.copy_to_2d_ptr(dst.as_mut_ptr()) }
+    }
+
+    // Copy the buffer's rows to `dst`, which must be valid for writes of `width * height`
+    // elements. Unlike `copy_to_2d`, the destination may be uninitialized.
+    pub(crate) unsafe fn copy_to_2d_ptr(&self, dst: *mut T) -> CudaResult<()> {
+        let width_bytes = self.width * size_of::<T>();
+        if width_bytes == 0 || self.height == 0 {
+            return Ok(());
+        }
+        let copy = CUDA_MEMCPY2D {
+            srcMemoryType: CUmemorytype::CU_MEMORYTYPE_DEVICE,
+            srcDevice: self.buf.as_raw() as CUdeviceptr,
+            srcPitch: self.pitch,
+            dstMemoryType: CUmemorytype::CU_MEMORYTYPE_HOST,
+            dstHost: dst as *mut c_void,
+            dstPitch: width_bytes,
+            WidthInBytes: width_bytes,
+            Height: self.height,
+            ..empty_copy_2d()
+        };
+        cuda::cuMemcpy2D_v2(&copy as *const CUDA_MEMCPY2D).to_result()
+    }
+
+    /// Destroy a `DevicePitchedBuffer`, returning an error.
+    ///
+    /// Deallocating device memory can return errors from previous asynchronous work. This function
+    /// destroys the given buffer and returns the error and the un-destroyed buffer on failure.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let x = unsafe { DevicePitchedBuffer::<f32>::uninitialized(16, 16).unwrap() };
+    /// match DevicePitchedBuffer::drop(x) {
+    ///     Ok(()) => println!("Successfully destroyed"),
+    ///     Err((e, buf)) => {
+    ///         println!("Failed to destroy buffer: {:?}", e);
+    ///         // Do something with buf
+    ///     },
+    /// }
+    /// ```
+    pub fn drop(mut buf: DevicePitchedBuffer<T>) -> DropResult<DevicePitchedBuffer<T>> {
+        if buf.buf.is_null() || !buf.is_allocated() {
+            return Ok(());
+        }
+
+        let ptr = mem::replace(&mut buf.buf, DevicePointer::null());
+        unsafe {
+            match cuda_free(ptr) {
+                Ok(()) => {
+                    mem::forget(buf);
+                    Ok(())
+                }
+                Err(e) => {
+                    buf.buf = ptr;
+                    Err((e, buf))
+                }
+            }
+        }
+    }
+
+    fn is_allocated(&self) -> bool {
+        self.width * size_of::<T>() > 0 && self.height > 0
+    }
+}
+impl<T: DeviceCopy> crate::private::Sealed for DevicePitchedBuffer<T> {}
+impl<T: DeviceCopy> AnyDeviceBuffer for DevicePitchedBuffer<T> {
+    fn len_bytes(&self) -> usize {
+        self.pitch * self.height
+    }
+
+    fn device_ptr_raw(&self) -> CUdeviceptr {
+        self.buf.as_raw() as CUdeviceptr
+    }
+
+    fn element_size(&self) -> usize {
+        size_of::<T>()
+    }
+}
+impl<T: DeviceCopy> Drop for DevicePitchedBuffer<T> {
+    fn drop(&mut self) {
+        if self.buf.is_null() || !self.is_allocated() {
+            return;
+        }
+
+        // No choice but to panic if this fails.
+        let ptr = mem::replace(&mut self.buf, DevicePointer::null());
+        unsafe {
+            cuda_free(ptr).expect("Failed to deallocate CUDA Device memory.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_device_pitched_buffer {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug)]
+    struct ZeroSizedType;
+    unsafe impl DeviceCopy for ZeroSizedType {}
+
+    #[test]
+    fn test_round_trip_with_padding() {
+        let _context = crate::quick_init().unwrap();
+        // Three-byte rows are far smaller than the driver's row alignment, so they get padded.
+        let mut buffer = unsafe { DevicePitchedBuffer::<u8>::uninitialized(3, 4).unwrap() };
+        assert_eq!(3, buffer.width());
+        assert_eq!(4, buffer.height());
+        assert!(buffer.pitch() > 3);
+
+        let data: Vec<u8> = (0..12).collect();
+        buffer.copy_from_2d(&data).unwrap();
+        let mut result = vec![0u8; 12];
+        buffer.copy_to_2d(&mut result).unwrap();
+        assert_eq!(data, result);
+        DevicePitchedBuffer::drop(buffer).unwrap();
+    }
+
+    #[test]
+    fn test_empty_buffers_do_not_allocate() {
+        let _context = crate::quick_init().unwrap();
+        let mut buffer = unsafe { DevicePitchedBuffer::<f32>::uninitialized(0, 4).unwrap() };
+        assert_eq!(0, buffer.pitch());
+        buffer.copy_from_2d(&[]).unwrap();
+        drop(buffer);
+
+        let buffer = unsafe { DevicePitchedBuffer::<f32>::uninitialized(4, 0).unwrap() };
+        drop(buffer);
+
+        let buffer = unsafe { DevicePitchedBuffer::<ZeroSizedType>::uninitialized(4, 4).unwrap() };
+        drop(buffer);
+    }
+
+    #[test]
+    fn test_overflows_usize() {
+        let _context = crate::quick_init().unwrap();
+        let err =
+            unsafe { DevicePitchedBuffer::<u64>::uninitialized(usize::MAX - 1, 1) }.unwrap_err();
+        assert_eq!(CudaError::InvalidMemoryAllocation, err);
+    }
+}
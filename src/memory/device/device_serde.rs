@@ -0,0 +1,77 @@
+use crate::memory::device::DeviceBuffer;
+use crate::memory::DeviceCopy;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{self, Serialize, Serializer};
+
+/// Serializes the contents of the buffer as a sequence, by downloading them to a host `Vec`.
+///
+/// Only available with the `serde` feature.
+///
+/// The buffer's context must be current on the serializing thread. Since `Serialize` has no way
+/// to take a context, a failed download is reported as a serialization error rather than a
+/// panic, with the CUDA error as its message.
+impl<T: DeviceCopy + Serialize> Serialize for DeviceBuffer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.copy_to_vec()
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// Deserializes a sequence and uploads it into a newly-allocated buffer.
+///
+/// Only available with the `serde` feature.
+///
+/// The buffer is allocated in the context which is current on the deserializing thread. If
+/// allocating or uploading fails, the CUDA error is reported as a deserialization error.
+///
+/// # Examples:
+///
+/// ```
+/// # let _context = rustacuda::quick_init().unwrap();
+/// use rustacuda::memory::*;
+/// let buffer = DeviceBuffer::from_slice(&[1u32, 2, 3]).unwrap();
+/// let json = serde_json::to_string(&buffer).unwrap();
+/// assert_eq!("[1,2,3]", json);
+///
+/// let restored: DeviceBuffer<u32> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(vec![1, 2, 3], restored.copy_to_vec().unwrap());
+/// ```
+impl<'de, T: DeviceCopy + Deserialize<'de>> Deserialize<'de> for DeviceBuffer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        DeviceBuffer::from_slice(&values).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test_device_serde {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        let _context = crate::quick_init().unwrap();
+        let values: Vec<f64> = (0..100).map(|x| x as f64 * 0.5).collect();
+        let buffer = DeviceBuffer::from_slice(&values).unwrap();
+        let json = serde_json::to_string(&buffer).unwrap();
+        let restored: DeviceBuffer<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(values, restored.copy_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_empty_round_trip() {
+        let _context = crate::quick_init().unwrap();
+        let buffer = DeviceBuffer::<u32>::from_slice(&[]).unwrap();
+        let json = serde_json::to_string(&buffer).unwrap();
+        assert_eq!("[]", json);
+        let restored: DeviceBuffer<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(0, restored.len());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let _context = crate::quick_init().unwrap();
+        let result: Result<DeviceBuffer<u32>, _> = serde_json::from_str("[1, -2]");
+        assert!(result.is_err());
+    }
+}
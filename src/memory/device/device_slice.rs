@@ -1,8 +1,15 @@
-use crate::error::{CudaResult, ToResult};
-use crate::memory::device::{CopyDestination, DeviceBuffer};
+use crate::context::ContextHandle;
+use crate::error::{CudaError, CudaResult, ToResult};
+use crate::event::{Event, EventFlags};
+use crate::memory::device::{AsyncCopyDestination, ByteSwap, CopyDestination, DeviceBuffer};
 use crate::memory::DeviceCopy;
 use crate::memory::DevicePointer;
+use crate::memory::LockedBuffer;
+use crate::memory::{HostRegistered, PinnedVec};
+use crate::stream::Stream;
 use cuda_sys::cuda;
+use std::cmp;
+use std::io::{self, Write};
 use std::iter::{ExactSizeIterator, FusedIterator};
 use std::mem;
 use std::ops::{
@@ -10,6 +17,7 @@ use std::ops::{
 };
 
 use std::os::raw::c_void;
+use std::ptr;
 use std::slice::{self, Chunks, ChunksMut};
 
 /// Fixed-size device-side slice.
@@ -297,6 +305,386 @@ impl<T> DeviceSlice<T> {
         DeviceSlice::from_slice_mut(slice::from_raw_parts_mut(data.as_raw_mut(), len))
     }
 }
+impl<T: DeviceCopy> DeviceSlice<T> {
+    /// Copy the contents of this slice into a newly-allocated host vector.
+    ///
+    /// # Errors:
+    ///
+    /// If the copy fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let buffer = DeviceBuffer::from_slice(&[1u64, 2, 3]).unwrap();
+    /// assert_eq!(vec![1u64, 2, 3], buffer.copy_to_vec().unwrap());
+    /// ```
+    pub fn copy_to_vec(&self) -> CudaResult<Vec<T>> {
+        let len = self.len();
+        let mut vec = Vec::with_capacity(len);
+        let size = size_of::<T>() * len;
+        unsafe {
+            if size != 0 {
+                cuda::cuMemcpyDtoH_v2(vec.as_mut_ptr() as *mut c_void, self.as_ptr() as u64, size)
+                    .to_result()?;
+            }
+            vec.set_len(len);
+        }
+        Ok(vec)
+    }
+
+    /// Returns an iterator which downloads this slice `chunk_size` elements at a time and yields
+    /// the elements by value.
+    ///
+    /// Only one chunk is held on the host at a time, in a staging buffer which is reused for each
+    /// chunk, so this can be used to inspect buffers too large to copy to the host at once. Each
+    /// chunk costs a separate blocking copy, so this is a convenience for debugging and testing
+    /// rather than for performance-sensitive code.
+    ///
+    /// If a copy fails, the iterator yields the error and then ends.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let buffer = DeviceBuffer::from_slice(&[1u64, 2, 3, 4, 5]).unwrap();
+    /// let sum = buffer
+    ///     .iter_copied(2)
+    ///     .sum::<Result<u64, _>>()
+    ///     .unwrap();
+    /// assert_eq!(15, sum);
+    /// ```
+    pub fn iter_copied(&self, chunk_size: usize) -> DeviceIterCopied<'_, T> {
+        DeviceIterCopied {
+            chunks: self.chunks(chunk_size),
+            staging: Vec::with_capacity(chunk_size),
+            next: 0,
+            failed: false,
+        }
+    }
+
+    /// Write the raw bytes of this slice to `writer`, downloading it in chunks on `stream`.
+    ///
+    /// The data is copied through two reused page-locked staging buffers. While one chunk is
+    /// being written to `writer`, the next is already being copied from the device into the other
+    /// buffer, so the transfers overlap with the I/O. Only the staging buffers are held in host
+    /// memory, so this is suitable for checkpointing buffers larger than would fit in a `Vec`.
+    /// Elements are written in host byte order, including any padding bytes.
+    ///
+    /// This blocks until all of the data has been written. Work already queued on `stream` is
+    /// completed before the slice is read.
+    ///
+    /// # Errors:
+    ///
+    /// If writing fails, returns the error from `writer`. If allocating the staging buffers or a
+    /// copy fails, returns the CUDA error wrapped in an `io::Error`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+    /// let buffer = DeviceBuffer::from_slice(&[1u8, 2, 3, 4]).unwrap();
+    /// let mut bytes = Vec::new();
+    /// buffer.write_to(&mut bytes, &stream).unwrap();
+    /// assert_eq!(vec![1u8, 2, 3, 4], bytes);
+    /// ```
+    pub fn write_to<W: Write>(&self, mut writer: W, stream: &Stream) -> io::Result<()> {
+        let size = size_of::<T>();
+        if size == 0 || self.is_empty() {
+            return Ok(());
+        }
+        let cuda_error = io::Error::other;
+
+        // Stage about a megabyte at a time.
+        let chunk_len = cmp::max(1, cmp::min(self.len(), (1 << 20) / size));
+        let mut staging = unsafe {
+            [
+                LockedBuffer::<T>::uninitialized(chunk_len).map_err(cuda_error)?,
+                LockedBuffer::<T>::uninitialized(chunk_len).map_err(cuda_error)?,
+            ]
+        };
+        let copied = [
+            Event::new(EventFlags::DISABLE_TIMING).map_err(cuda_error)?,
+            Event::new(EventFlags::DISABLE_TIMING).map_err(cuda_error)?,
+        ];
+        let download = |chunk: &DeviceSlice<T>, staging: &mut LockedBuffer<T>, copied: &Event| {
+            unsafe {
+                cuda::cuMemcpyDtoHAsync_v2(
+                    staging.as_mut_ptr() as *mut c_void,
+                    chunk.as_ptr() as u64,
+                    chunk.len() * size,
+                    stream.as_inner(),
+                )
+                .to_result()?;
+            }
+            copied.record(stream)
+        };
+
+        let mut write_chunks = || {
+            let mut chunks = self.chunks(chunk_len).peekable();
+            let mut current = 0;
+            if let Some(chunk) = chunks.peek() {
+                download(chunk, &mut staging[0], &copied[0]).map_err(cuda_error)?;
+            }
+            while let Some(chunk) = chunks.next() {
+                // Start copying the next chunk into the other buffer, which has already been
+                // written.
+                if let Some(next) = chunks.peek() {
+                    download(next, &mut staging[1 - current], &copied[1 - current])
+                        .map_err(cuda_error)?;
+                }
+                copied[current].synchronize().map_err(cuda_error)?;
+                let bytes = unsafe {
+                    slice::from_raw_parts(
+                        staging[current].as_ptr() as *const u8,
+                        chunk.len() * size,
+                    )
+                };
+                writer.write_all(bytes)?;
+                current = 1 - current;
+            }
+            Ok(())
+        };
+        let result = write_chunks();
+        if result.is_err() {
+            // A download into the other staging buffer may still be in flight, so it has to finish
+            // before the buffers are freed. If waiting for it fails, leak them instead.
+            if stream.synchronize().is_err() {
+                mem::forget(staging);
+            }
+        }
+        result
+    }
+
+    /// Copy the contents of this slice to a new host vector, reversing the byte order of each
+    /// element.
+    ///
+    /// This is useful when the device data must be written out in a different endianness than
+    /// the host's, for example when producing big-endian file formats.
+    ///
+    /// # Errors:
+    ///
+    /// If the copy fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let buffer = DeviceBuffer::from_slice(&[0x1234_5678u32]).unwrap();
+    /// let swapped = buffer.to_vec_swapped().unwrap();
+    /// assert_eq!(vec![0x7856_3412u32], swapped);
+    /// ```
+    pub fn to_vec_swapped(&self) -> CudaResult<Vec<T>>
+    where
+        T: ByteSwap,
+    {
+        let vec = self.copy_to_vec()?;
+        Ok(vec.into_iter().map(ByteSwap::byte_swap).collect())
+    }
+
+    /// Download the contents of this slice and partition its indices by a host-side predicate.
+    ///
+    /// Returns the indices of the elements for which `pred` returned `true`, followed by those for
+    /// which it returned `false`. Both are in ascending order. This copies the whole slice to the
+    /// host, so it is intended for tests and debugging rather than performance-sensitive code.
+    ///
+    /// # Errors:
+    ///
+    /// If the copy fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let buffer = DeviceBuffer::from_slice(&[1u32, 2, 3, 4]).unwrap();
+    /// let (even, odd) = buffer.partition_indices_host(|x| x % 2 == 0).unwrap();
+    /// assert_eq!(vec![1, 3], even);
+    /// assert_eq!(vec![0, 2], odd);
+    /// ```
+    pub fn partition_indices_host<F>(&self, mut pred: F) -> CudaResult<(Vec<usize>, Vec<usize>)>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let vec = self.copy_to_vec()?;
+        Ok((0..vec.len()).partition(|&i| pred(&vec[i])))
+    }
+
+    /// View this slice as a slice of its bytes, such as for raw I/O or for passing to a library
+    /// which takes untyped device memory.
+    ///
+    /// The length of the returned slice is `self.len() * mem::size_of::<T>()`. A byte slice has
+    /// no alignment requirement, so when converting part of it back into a `DeviceSlice<U>` with
+    /// [`from_raw_parts`](#method.from_raw_parts), the caller must ensure that it starts at a
+    /// multiple of `mem::align_of::<U>()` bytes from the start of the allocation.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let buffer = DeviceBuffer::from_slice(&[1u32, 2, 3]).unwrap();
+    /// let bytes = buffer.as_bytes();
+    /// assert_eq!(12, bytes.len());
+    /// ```
+    pub fn as_bytes(&self) -> &DeviceSlice<u8> {
+        unsafe {
+            DeviceSlice::from_slice(slice::from_raw_parts(
+                self.as_ptr() as *const u8,
+                size_of_val(&self.0),
+            ))
+        }
+    }
+
+    /// View this slice as a mutable slice of its bytes.
+    ///
+    /// See [`as_bytes`](#method.as_bytes) for details.
+    ///
+    /// # Safety
+    ///
+    /// `DeviceCopy` does not guarantee that every bit pattern is a valid value, so the caller must
+    /// ensure that the bytes written through the returned slice form valid values of `T`. This
+    /// always holds when `T` is an integer or floating-point type, but not when `T` is, for
+    /// example, `bool`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut buffer = DeviceBuffer::from_slice(&[0u32; 2]).unwrap();
+    /// let bytes = unsafe { buffer.as_bytes_mut() };
+    /// bytes.copy_from(&[0xFFu8; 8]).unwrap();
+    /// assert_eq!(vec![0xFFFF_FFFFu32; 2], buffer.copy_to_vec().unwrap());
+    /// ```
+    pub unsafe fn as_bytes_mut(&mut self) -> &mut DeviceSlice<u8> {
+        DeviceSlice::from_slice_mut(slice::from_raw_parts_mut(
+            self.as_mut_ptr() as *mut u8,
+            size_of_val(&self.0),
+        ))
+    }
+
+    /// Copy `src`, which was allocated in the context `src_ctx`, into this slice, which was
+    /// allocated in the context `dst_ctx`.
+    ///
+    /// This copies directly between devices when peer access has been enabled with
+    /// [`Context::enable_peer_access`](../context/struct.Context.html#method.enable_peer_access),
+    /// and otherwise stages the copy through host memory.
+    ///
+    /// # Errors:
+    ///
+    /// Returns `InvalidValue` if the slices have different lengths. If the copy fails, returns
+    /// the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let src = DeviceBuffer::from_slice(&[1u32, 2, 3]).unwrap();
+    /// let mut dst = DeviceBuffer::from_slice(&[0u32; 3]).unwrap();
+    /// // Usually `src` and `dst` would be in contexts on different devices.
+    /// dst.copy_peer(&context, &src, &context).unwrap();
+    /// assert_eq!(vec![1, 2, 3], dst.copy_to_vec().unwrap());
+    /// ```
+    pub fn copy_peer<C: ContextHandle, D: ContextHandle>(
+        &mut self,
+        dst_ctx: &C,
+        src: &DeviceSlice<T>,
+        src_ctx: &D,
+    ) -> CudaResult<()> {
+        if self.len() != src.len() {
+            return Err(CudaError::InvalidValue);
+        }
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            unsafe {
+                cuda::cuMemcpyPeer(
+                    self.0.as_mut_ptr() as u64,
+                    dst_ctx.get_inner(),
+                    src.as_ptr() as u64,
+                    src_ctx.get_inner(),
+                    size,
+                )
+                .to_result()?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Asynchronously copy `src`, allocated in the context `src_ctx`, into `dst`, allocated in the
+/// context `dst_ctx`.
+///
+/// This is the asynchronous version of [`DeviceSlice::copy_peer`](struct.DeviceSlice.html#method.copy_peer),
+/// and allows transfers between devices to overlap with work on other streams. Peer access from
+/// `dst_ctx` to `src_ctx` should be enabled first with
+/// [`Context::enable_peer_access`](../context/struct.Context.html#method.enable_peer_access);
+/// without it, the driver stages the copy through host memory and it may not overlap with other
+/// work.
+///
+/// # Errors:
+///
+/// Returns `InvalidValue` if the slices have different lengths. If the copy fails, returns the
+/// error from CUDA.
+///
+/// # Safety
+///
+/// This function returns before the copy has completed. As with
+/// [`AsyncCopyDestination`](trait.AsyncCopyDestination.html), the caller must ensure that neither
+/// slice is deallocated or accessed by other operations, and that `src` is not modified, until
+/// `stream` has been synchronized.
+///
+/// # Examples:
+///
+/// ```
+/// # let context = rustacuda::quick_init().unwrap();
+/// use rustacuda::memory::*;
+/// use rustacuda::stream::{Stream, StreamFlags};
+/// let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+/// let src = DeviceBuffer::from_slice(&[1u32, 2, 3]).unwrap();
+/// let mut dst = DeviceBuffer::from_slice(&[0u32; 3]).unwrap();
+/// // Usually `src` and `dst` would be in contexts on different devices.
+/// unsafe {
+///     copy_peer_async(&mut dst, &context, &src, &context, &stream).unwrap();
+/// }
+/// stream.synchronize().unwrap();
+/// assert_eq!(vec![1, 2, 3], dst.copy_to_vec().unwrap());
+/// ```
+pub unsafe fn copy_peer_async<T: DeviceCopy, C: ContextHandle, D: ContextHandle>(
+    dst: &mut DeviceSlice<T>,
+    dst_ctx: &C,
+    src: &DeviceSlice<T>,
+    src_ctx: &D,
+    stream: &Stream,
+) -> CudaResult<()> {
+    if dst.len() != src.len() {
+        return Err(CudaError::InvalidValue);
+    }
+    let size = size_of::<T>() * dst.len();
+    if size != 0 {
+        cuda::cuMemcpyPeerAsync(
+            dst.as_mut_ptr() as u64,
+            dst_ctx.get_inner(),
+            src.as_ptr() as u64,
+            src_ctx.get_inner(),
+            size,
+            stream.as_inner(),
+        )
+        .to_result()?
+    }
+    stream.sync_if_forced()
+}
 
 /// An iterator over a [`DeviceSlice`](struct.DeviceSlice.html) in (non-overlapping) chunks
 /// (`chunk_size` elements at a time).
@@ -348,6 +736,64 @@ impl<'a, T> DoubleEndedIterator for DeviceChunks<'a, T> {
 impl<'a, T> ExactSizeIterator for DeviceChunks<'a, T> {}
 impl<'a, T> FusedIterator for DeviceChunks<'a, T> {}
 
+/// An iterator which downloads a [`DeviceSlice`](struct.DeviceSlice.html) one chunk at a time
+/// and yields its elements by value.
+///
+/// This struct is created by the `iter_copied` method on `DeviceSlices`.
+#[derive(Debug)]
+pub struct DeviceIterCopied<'a, T: 'a> {
+    chunks: DeviceChunks<'a, T>,
+    // Holds the current chunk. Elements before `next` have already been moved out.
+    staging: Vec<T>,
+    next: usize,
+    failed: bool,
+}
+impl<'a, T: DeviceCopy> Iterator for DeviceIterCopied<'a, T> {
+    type Item = CudaResult<T>;
+
+    fn next(&mut self) -> Option<CudaResult<T>> {
+        if self.failed {
+            return None;
+        }
+        if self.next == self.staging.len() {
+            let chunk = self.chunks.next()?;
+            let size = size_of::<T>() * chunk.len();
+            unsafe {
+                self.staging.set_len(0);
+                self.next = 0;
+                if size != 0 {
+                    let result = cuda::cuMemcpyDtoH_v2(
+                        self.staging.as_mut_ptr() as *mut c_void,
+                        chunk.as_ptr() as u64,
+                        size,
+                    )
+                    .to_result();
+                    if let Err(e) = result {
+                        self.failed = true;
+                        return Some(Err(e));
+                    }
+                }
+                self.staging.set_len(chunk.len());
+            }
+        }
+        let value = unsafe { ptr::read(self.staging.as_ptr().add(self.next)) };
+        self.next += 1;
+        Some(Ok(value))
+    }
+}
+impl<'a, T: DeviceCopy> FusedIterator for DeviceIterCopied<'a, T> {}
+impl<'a, T> Drop for DeviceIterCopied<'a, T> {
+    fn drop(&mut self) {
+        // Only drop the elements which haven't been moved out.
+        unsafe {
+            let remaining = self.staging.len() - self.next;
+            let tail = self.staging.as_mut_ptr().add(self.next);
+            self.staging.set_len(0);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(tail, remaining));
+        }
+    }
+}
+
 /// An iterator over a [`DeviceSlice`](struct.DeviceSlice.html) in (non-overlapping) mutable chunks
 /// (`chunk_size` elements at a time).
 ///
@@ -505,3 +951,168 @@ impl<T: DeviceCopy> CopyDestination<DeviceBuffer<T>> for DeviceSlice<T> {
         self.copy_to(val as &mut DeviceSlice<T>)
     }
 }
+impl<T: DeviceCopy> AsyncCopyDestination<LockedBuffer<T>> for DeviceSlice<T> {
+    unsafe fn async_copy_from(&mut self, val: &LockedBuffer<T>, stream: &Stream) -> CudaResult<()> {
+        assert!(
+            self.len() == val.len(),
+            "destination and source slices have different lengths"
+        );
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            cuda::cuMemcpyHtoDAsync_v2(
+                self.0.as_mut_ptr() as u64,
+                val.as_ptr() as *const c_void,
+                size,
+                stream.as_inner(),
+            )
+            .to_result()?
+        }
+        stream.sync_if_forced()
+    }
+
+    unsafe fn async_copy_to(&self, val: &mut LockedBuffer<T>, stream: &Stream) -> CudaResult<()> {
+        assert!(
+            self.len() == val.len(),
+            "destination and source slices have different lengths"
+        );
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            cuda::cuMemcpyDtoHAsync_v2(
+                val.as_mut_ptr() as *mut c_void,
+                self.as_ptr() as u64,
+                size,
+                stream.as_inner(),
+            )
+            .to_result()?
+        }
+        stream.sync_if_forced()
+    }
+}
+impl<T: DeviceCopy> AsyncCopyDestination<PinnedVec<T>> for DeviceSlice<T> {
+    unsafe fn async_copy_from(&mut self, val: &PinnedVec<T>, stream: &Stream) -> CudaResult<()> {
+        assert!(
+            self.len() == val.len(),
+            "destination and source slices have different lengths"
+        );
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            cuda::cuMemcpyHtoDAsync_v2(
+                self.0.as_mut_ptr() as u64,
+                val.as_ptr() as *const c_void,
+                size,
+                stream.as_inner(),
+            )
+            .to_result()?
+        }
+        stream.sync_if_forced()
+    }
+
+    unsafe fn async_copy_to(&self, val: &mut PinnedVec<T>, stream: &Stream) -> CudaResult<()> {
+        assert!(
+            self.len() == val.len(),
+            "destination and source slices have different lengths"
+        );
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            cuda::cuMemcpyDtoHAsync_v2(
+                val.as_mut_ptr() as *mut c_void,
+                self.as_ptr() as u64,
+                size,
+                stream.as_inner(),
+            )
+            .to_result()?
+        }
+        stream.sync_if_forced()
+    }
+}
+impl<'a, T: DeviceCopy> AsyncCopyDestination<HostRegistered<'a, T>> for DeviceSlice<T> {
+    unsafe fn async_copy_from(
+        &mut self,
+        val: &HostRegistered<'a, T>,
+        stream: &Stream,
+    ) -> CudaResult<()> {
+        assert!(
+            self.len() == val.len(),
+            "destination and source slices have different lengths"
+        );
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            cuda::cuMemcpyHtoDAsync_v2(
+                self.0.as_mut_ptr() as u64,
+                val.as_ptr() as *const c_void,
+                size,
+                stream.as_inner(),
+            )
+            .to_result()?
+        }
+        stream.sync_if_forced()
+    }
+
+    unsafe fn async_copy_to(
+        &self,
+        val: &mut HostRegistered<'a, T>,
+        stream: &Stream,
+    ) -> CudaResult<()> {
+        assert!(
+            self.len() == val.len(),
+            "destination and source slices have different lengths"
+        );
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            cuda::cuMemcpyDtoHAsync_v2(
+                val.as_mut_ptr() as *mut c_void,
+                self.as_ptr() as u64,
+                size,
+                stream.as_inner(),
+            )
+            .to_result()?
+        }
+        stream.sync_if_forced()
+    }
+}
+impl<T: DeviceCopy> AsyncCopyDestination<DeviceSlice<T>> for DeviceSlice<T> {
+    unsafe fn async_copy_from(&mut self, val: &DeviceSlice<T>, stream: &Stream) -> CudaResult<()> {
+        assert!(
+            self.len() == val.len(),
+            "destination and source slices have different lengths"
+        );
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            cuda::cuMemcpyDtoDAsync_v2(
+                self.0.as_mut_ptr() as u64,
+                val.as_ptr() as u64,
+                size,
+                stream.as_inner(),
+            )
+            .to_result()?
+        }
+        stream.sync_if_forced()
+    }
+
+    unsafe fn async_copy_to(&self, val: &mut DeviceSlice<T>, stream: &Stream) -> CudaResult<()> {
+        assert!(
+            self.len() == val.len(),
+            "destination and source slices have different lengths"
+        );
+        let size = size_of::<T>() * self.len();
+        if size != 0 {
+            cuda::cuMemcpyDtoDAsync_v2(
+                val.as_mut_ptr() as u64,
+                self.as_ptr() as u64,
+                size,
+                stream.as_inner(),
+            )
+            .to_result()?
+        }
+        stream.sync_if_forced()
+    }
+}
+impl<T: DeviceCopy> AsyncCopyDestination<DeviceBuffer<T>> for DeviceSlice<T> {
+    unsafe fn async_copy_from(&mut self, val: &DeviceBuffer<T>, stream: &Stream) -> CudaResult<()> {
+        self.async_copy_from(val as &DeviceSlice<T>, stream)
+    }
+
+    unsafe fn async_copy_to(&self, val: &mut DeviceBuffer<T>, stream: &Stream) -> CudaResult<()> {
+        self.async_copy_to(val as &mut DeviceSlice<T>, stream)
+    }
+}
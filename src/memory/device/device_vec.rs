@@ -0,0 +1,221 @@
+use crate::error::{CudaError, CudaResult, DropResult};
+use crate::memory::device::{AnyDeviceBuffer, CopyDestination, DeviceBuffer, DeviceSlice};
+use crate::memory::DeviceCopy;
+use crate::memory::DevicePointer;
+use cuda_sys::cuda::CUdeviceptr;
+use std::cmp;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+
+/// Growable array in device memory, similar to `Vec`.
+///
+/// Unlike [`DeviceBuffer`](struct.DeviceBuffer.html), a `DeviceVec` can grow as data is appended
+/// to it. When the capacity is exceeded, a new allocation of at least twice the size is made, the
+/// existing elements are copied into it on the device and the old allocation is freed. As with
+/// `Vec`, this invalidates any pointers into the old allocation.
+#[derive(Debug)]
+pub struct DeviceVec<T: DeviceCopy> {
+    buf: DeviceBuffer<T>,
+    len: usize,
+}
+impl<T: DeviceCopy> DeviceVec<T> {
+    /// Create a new, empty `DeviceVec`.
+    ///
+    /// This does not allocate any memory until data is appended to it.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let vec = DeviceVec::<u64>::new();
+    /// assert_eq!(0, vec.len());
+    /// ```
+    pub fn new() -> Self {
+        DeviceVec {
+            buf: unsafe {
+                DeviceBuffer::from_raw_parts(
+                    DevicePointer::wrap(ptr::NonNull::dangling().as_ptr()),
+                    0,
+                )
+            },
+            len: 0,
+        }
+    }
+
+    /// Create a new, empty `DeviceVec` with space for at least `capacity` elements.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA. If `capacity` is large enough that
+    /// `capacity * mem::sizeof::<T>()` overflows usize, then returns InvalidMemoryAllocation.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let vec = DeviceVec::<u64>::with_capacity(10).unwrap();
+    /// assert!(vec.capacity() >= 10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> CudaResult<Self> {
+        let mut vec = DeviceVec::new();
+        vec.reserve(capacity)?;
+        Ok(vec)
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Reserve capacity for at least `additional` more elements.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation or the copy of the existing elements fails, returns the error from CUDA.
+    /// If the new capacity would overflow usize, returns InvalidMemoryAllocation.
+    pub fn reserve(&mut self, additional: usize) -> CudaResult<()> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(CudaError::InvalidMemoryAllocation)?;
+        if required <= self.capacity() {
+            return Ok(());
+        }
+
+        let new_capacity = cmp::max(required, self.capacity().saturating_mul(2));
+        let mut new_buf = unsafe { DeviceBuffer::uninitialized(new_capacity)? };
+        new_buf[..self.len].copy_from(&self.buf[..self.len])?;
+        self.buf = new_buf;
+        Ok(())
+    }
+
+    /// Append the contents of a device slice to the end of the vector, growing the allocation if
+    /// necessary.
+    ///
+    /// The data is copied directly from device to device, without passing through the host.
+    ///
+    /// # Errors:
+    ///
+    /// If a reallocation is needed and fails, or if the copy fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let first = DeviceBuffer::from_slice(&[1u32, 2]).unwrap();
+    /// let second = DeviceBuffer::from_slice(&[3u32]).unwrap();
+    ///
+    /// let mut vec = DeviceVec::new();
+    /// vec.extend_from_device(&first).unwrap();
+    /// vec.extend_from_device(&second).unwrap();
+    /// assert_eq!(vec![1u32, 2, 3], vec.copy_to_vec().unwrap());
+    /// ```
+    pub fn extend_from_device(&mut self, src: &DeviceSlice<T>) -> CudaResult<()> {
+        self.reserve(src.len())?;
+        let end = self.len + src.len();
+        self.buf[self.len..end].copy_from(src)?;
+        self.len = end;
+        Ok(())
+    }
+
+    /// Destroy a `DeviceVec`, returning an error.
+    ///
+    /// Deallocating device memory can return errors from previous asynchronous work. This function
+    /// destroys the given vector and returns the error and the un-destroyed vector on failure.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut x = DeviceVec::new();
+    /// x.extend_from_device(&DeviceBuffer::from_slice(&[10u32]).unwrap()).unwrap();
+    /// match DeviceVec::drop(x) {
+    ///     Ok(()) => println!("Successfully destroyed"),
+    ///     Err((e, vec)) => {
+    ///         println!("Failed to destroy vector: {:?}", e);
+    ///         // Do something with vec
+    ///     },
+    /// }
+    /// ```
+    pub fn drop(vec: DeviceVec<T>) -> DropResult<DeviceVec<T>> {
+        let len = vec.len;
+        DeviceBuffer::drop(vec.buf).map_err(|(e, buf)| (e, DeviceVec { buf, len }))
+    }
+}
+impl<T: DeviceCopy> Default for DeviceVec<T> {
+    fn default() -> Self {
+        DeviceVec::new()
+    }
+}
+impl<T: DeviceCopy> crate::private::Sealed for DeviceVec<T> {}
+impl<T: DeviceCopy> AnyDeviceBuffer for DeviceVec<T> {
+    fn len_bytes(&self) -> usize {
+        self.len * size_of::<T>()
+    }
+
+    fn device_ptr_raw(&self) -> CUdeviceptr {
+        self.buf.device_ptr_raw()
+    }
+
+    fn element_size(&self) -> usize {
+        size_of::<T>()
+    }
+}
+impl<T: DeviceCopy> Deref for DeviceVec<T> {
+    type Target = DeviceSlice<T>;
+
+    fn deref(&self) -> &DeviceSlice<T> {
+        &self.buf[..self.len]
+    }
+}
+impl<T: DeviceCopy> DerefMut for DeviceVec<T> {
+    fn deref_mut(&mut self) -> &mut DeviceSlice<T> {
+        &mut self.buf[..self.len]
+    }
+}
+
+#[cfg(test)]
+mod test_device_vec {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct ZeroSizedType;
+    unsafe impl DeviceCopy for ZeroSizedType {}
+
+    #[test]
+    fn test_new_is_empty() {
+        let _context = crate::quick_init().unwrap();
+        let vec = DeviceVec::<u64>::new();
+        assert_eq!(0, vec.len());
+        assert_eq!(0, vec.capacity());
+        assert!(vec.copy_to_vec().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_extend_from_device() {
+        let _context = crate::quick_init().unwrap();
+        let first = DeviceBuffer::from_slice(&[1u64, 2, 3]).unwrap();
+        let second = DeviceBuffer::from_slice(&[4u64, 5, 6, 7, 8]).unwrap();
+
+        let mut vec = DeviceVec::with_capacity(2).unwrap();
+        vec.extend_from_device(&first).unwrap();
+        vec.extend_from_device(&second[1..]).unwrap();
+        assert_eq!(7, vec.len());
+        assert!(vec.capacity() >= 7);
+        assert_eq!(vec![1u64, 2, 3, 5, 6, 7, 8], vec.copy_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_extend_from_device_zero_sized() {
+        let _context = crate::quick_init().unwrap();
+        let src = DeviceBuffer::from_slice(&[ZeroSizedType; 4]).unwrap();
+        let mut vec = DeviceVec::new();
+        vec.extend_from_device(&src).unwrap();
+        vec.extend_from_device(&src).unwrap();
+        assert_eq!(8, vec.len());
+    }
+}
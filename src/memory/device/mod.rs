@@ -1,12 +1,27 @@
 use crate::error::CudaResult;
+use crate::memory::DeviceCopy;
+use crate::stream::Stream;
+use cuda_sys::cuda::CUdeviceptr;
 
 mod device_box;
 mod device_buffer;
+mod device_counter;
+#[cfg(feature = "ndarray")]
+mod device_ndarray;
+mod device_pitched_buffer;
+#[cfg(feature = "serde")]
+mod device_serde;
 mod device_slice;
+mod device_vec;
+mod scratch_manager;
 
 pub use self::device_box::*;
 pub use self::device_buffer::*;
+pub use self::device_counter::*;
+pub use self::device_pitched_buffer::*;
 pub use self::device_slice::*;
+pub use self::device_vec::*;
+pub use self::scratch_manager::*;
 
 /// Sealed trait implemented by types which can be the source or destination when copying data
 /// to/from the device or from one device allocation to another.
@@ -25,3 +40,113 @@ pub trait CopyDestination<O: ?Sized>: crate::private::Sealed {
     /// If a CUDA error occurs, return the error.
     fn copy_to(&self, dest: &mut O) -> CudaResult<()>;
 }
+
+/// Sealed trait implemented by types which can be the source or destination when copying data
+/// asynchronously to/from the device or from one device allocation to another.
+///
+/// Asynchronous copies to or from the host are only offered for page-locked host memory (such as
+/// [`LockedBuffer`](struct.LockedBuffer.html)), since the driver cannot perform a truly
+/// asynchronous transfer from pageable memory.
+///
+/// ## Safety
+///
+/// The functions of this trait are unsafe because they return control to the calling code while
+/// the copy operation could still be occurring in the background. This could allow calling code
+/// to read, modify or deallocate the destination buffer, or to modify or deallocate the source
+/// buffer resulting in a data race and undefined behavior.
+///
+/// Thus to enforce safety, the following invariants must be upheld:
+/// * The source and destination are not deallocated
+/// * The source is not modified
+/// * The destination is not written or read by any other operation
+///
+/// These invariants must be preserved until the stream is synchronized or an event queued after
+/// the copy is triggered has completed.
+pub trait AsyncCopyDestination<O: ?Sized>: crate::private::Sealed {
+    /// Asynchronously copy data from `source`. `source` must be the same size as `self`.
+    ///
+    /// Host memory used as a source or destination must be page-locked.
+    ///
+    /// # Safety
+    ///
+    /// For why this function is unsafe, see [AsyncCopyDestination](trait.AsyncCopyDestination.html)
+    ///
+    /// # Errors:
+    ///
+    /// If a CUDA error occurs, return the error.
+    unsafe fn async_copy_from(&mut self, source: &O, stream: &Stream) -> CudaResult<()>;
+
+    /// Asynchronously copy data to `dest`. `dest` must be the same size as `self`.
+    ///
+    /// Host memory used as a source or destination must be page-locked.
+    ///
+    /// # Safety
+    ///
+    /// For why this function is unsafe, see [AsyncCopyDestination](trait.AsyncCopyDestination.html)
+    ///
+    /// # Errors:
+    ///
+    /// If a CUDA error occurs, return the error.
+    unsafe fn async_copy_to(&self, dest: &mut O, stream: &Stream) -> CudaResult<()>;
+}
+
+/// Sealed, object-safe trait implemented by all of the typed device memory containers.
+///
+/// This allows device allocations of different element types to be stored together, for example
+/// as `Vec<Box<dyn AnyDeviceBuffer>>`, while still being able to pass their pointers to kernels.
+///
+/// # Examples:
+///
+/// ```
+/// # let _context = rustacuda::quick_init().unwrap();
+/// use rustacuda::memory::*;
+/// let buffers: Vec<Box<dyn AnyDeviceBuffer>> = vec![
+///     Box::new(DeviceBuffer::from_slice(&[0u8; 16]).unwrap()),
+///     Box::new(DeviceBox::new(&0u64).unwrap()),
+/// ];
+/// let total: usize = buffers.iter().map(|b| b.len_bytes()).sum();
+/// assert_eq!(24, total);
+/// ```
+pub trait AnyDeviceBuffer: crate::private::Sealed {
+    /// Returns the size of the allocation's contents in bytes.
+    ///
+    /// For a [`DevicePitchedBuffer`](struct.DevicePitchedBuffer.html) this includes the padding
+    /// at the end of each row, and for a [`DeviceVec`](struct.DeviceVec.html) it only includes the
+    /// elements, not the spare capacity.
+    fn len_bytes(&self) -> usize;
+
+    /// Returns the raw device address of the first element.
+    ///
+    /// The caller must ensure that the buffer outlives any use of the address, and that it is not
+    /// dereferenced by the CPU.
+    fn device_ptr_raw(&self) -> CUdeviceptr;
+
+    /// Returns the size of a single element in bytes.
+    fn element_size(&self) -> usize;
+}
+
+/// Trait for values whose byte order can be reversed, used when converting device data to or from
+/// a different endianness.
+///
+/// This is implemented for the primitive integer types.
+pub trait ByteSwap: DeviceCopy {
+    /// Returns `self` with the order of its bytes reversed.
+    fn byte_swap(self) -> Self;
+}
+
+macro_rules! impl_byte_swap {
+    ($($t:ty)*) => {
+        $(
+            impl ByteSwap for $t {
+                fn byte_swap(self) -> Self {
+                    self.swap_bytes()
+                }
+            }
+        )*
+    }
+}
+
+impl_byte_swap!(
+    usize u8 u16 u32 u64 u128
+    isize i8 i16 i32 i64 i128
+);
@@ -0,0 +1,137 @@
+use crate::error::{CudaError, CudaResult};
+use crate::memory::device::{DeviceBuffer, DeviceSlice};
+use crate::memory::DeviceCopy;
+use crate::memory::DevicePointer;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::ptr;
+
+/// Cache of reusable device-side scratch buffers, one per element type.
+///
+/// Kernels often need temporary workspace, such as the partial results of a reduction. Allocating
+/// that workspace for every launch is slow, so a `ScratchManager` keeps one buffer per type and
+/// hands out slices of it. A buffer is only reallocated when a request is larger than any previous
+/// request for that type; smaller requests reuse the existing allocation.
+///
+/// The contents of a scratch slice are unspecified. They may contain the results of earlier work
+/// which used the same buffer.
+#[derive(Debug, Default)]
+pub struct ScratchManager {
+    buffers: HashMap<TypeId, DeviceBuffer<u8>>,
+}
+impl ScratchManager {
+    /// Create a new, empty `ScratchManager`.
+    ///
+    /// This does not allocate any memory until scratch space is requested.
+    pub fn new() -> Self {
+        ScratchManager {
+            buffers: HashMap::new(),
+        }
+    }
+
+    /// Returns a scratch slice of `min_elements` `T`'s.
+    ///
+    /// If the cached buffer for `T` is too small, it is freed and replaced with one large enough
+    /// to hold `min_elements` elements. This invalidates any pointers into the old buffer.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA. If `min_elements` is large enough
+    /// that `min_elements * mem::size_of::<T>()` overflows usize, then returns
+    /// InvalidMemoryAllocation.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut scratch = ScratchManager::new();
+    /// let workspace = scratch.get::<f32>(1024).unwrap();
+    /// assert_eq!(1024, workspace.len());
+    /// ```
+    pub fn get<T: DeviceCopy + 'static>(
+        &mut self,
+        min_elements: usize,
+    ) -> CudaResult<&mut DeviceSlice<T>> {
+        let bytes = min_elements
+            .checked_mul(size_of::<T>())
+            .ok_or(CudaError::InvalidMemoryAllocation)?;
+        if bytes == 0 {
+            return Ok(unsafe {
+                DeviceSlice::from_raw_parts_mut(
+                    DevicePointer::wrap(ptr::NonNull::dangling().as_ptr()),
+                    min_elements,
+                )
+            });
+        }
+
+        let key = TypeId::of::<T>();
+        let too_small = match self.buffers.get(&key) {
+            Some(buf) => buf.len() < bytes,
+            None => true,
+        };
+        if too_small {
+            // Free the old buffer before allocating, so both are never alive at once.
+            drop(self.buffers.remove(&key));
+            let buf = unsafe { DeviceBuffer::uninitialized(bytes)? };
+            let _ = self.buffers.insert(key, buf);
+        }
+
+        let buf = self.buffers.get_mut(&key).unwrap();
+        // Device allocations are aligned for any type, so the bytes can be viewed as `T`'s.
+        unsafe {
+            Ok(DeviceSlice::from_raw_parts_mut(
+                buf.as_device_ptr().cast::<T>(),
+                min_elements,
+            ))
+        }
+    }
+
+    /// Returns the number of `T`'s the cached buffer for `T` can hold without reallocating.
+    pub fn capacity<T: 'static>(&self) -> usize {
+        match self.buffers.get(&TypeId::of::<T>()) {
+            Some(buf) if size_of::<T>() > 0 => buf.len() / size_of::<T>(),
+            _ => 0,
+        }
+    }
+
+    /// Free all cached scratch buffers.
+    pub fn clear(&mut self) {
+        self.buffers.clear();
+    }
+}
+
+#[cfg(test)]
+mod test_scratch_manager {
+    use super::*;
+
+    #[test]
+    fn test_grow_then_reuse() {
+        let _context = crate::quick_init().unwrap();
+        let mut scratch = ScratchManager::new();
+
+        assert_eq!(100, scratch.get::<f32>(100).unwrap().len());
+        assert_eq!(100, scratch.capacity::<f32>());
+
+        let grown = scratch.get::<f32>(1000).unwrap().as_device_ptr();
+        assert_eq!(1000, scratch.capacity::<f32>());
+
+        let smaller = scratch.get::<f32>(10).unwrap();
+        assert_eq!(10, smaller.len());
+        assert_eq!(grown, smaller.as_device_ptr());
+        assert_eq!(1000, scratch.capacity::<f32>());
+    }
+
+    #[test]
+    fn test_types_are_separate() {
+        let _context = crate::quick_init().unwrap();
+        let mut scratch = ScratchManager::new();
+        assert_eq!(16, scratch.get::<u64>(16).unwrap().len());
+        assert_eq!(4, scratch.get::<u8>(4).unwrap().len());
+        assert_eq!(16, scratch.capacity::<u64>());
+        assert_eq!(4, scratch.capacity::<u8>());
+
+        scratch.clear();
+        assert_eq!(0, scratch.capacity::<u64>());
+    }
+}
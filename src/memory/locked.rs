@@ -1,6 +1,8 @@
 use super::DeviceCopy;
 use crate::error::*;
-use crate::memory::malloc::{cuda_free_locked, cuda_malloc_locked};
+use crate::memory::malloc::{
+    cuda_free_locked, cuda_malloc_locked, cuda_malloc_locked_with_flags, HostAllocFlags,
+};
 use std::mem;
 use std::ops;
 use std::ptr;
@@ -42,6 +44,65 @@ impl<T: DeviceCopy + Clone> LockedBuffer<T> {
         }
     }
 
+    /// Allocate a new page-locked buffer with the given allocation flags, large enough to hold
+    /// `size` `T`'s and initialized with clones of `value`.
+    ///
+    /// Note that buffers allocated with `HostAllocFlags::WRITECOMBINED` are very slow to read from
+    /// the host; they should only be used for data which the host writes and the device reads.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA. If `size` is large enough that
+    /// `size * mem::sizeof::<T>()` overflows usize, then returns InvalidMemoryAllocation.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let flags = HostAllocFlags::PORTABLE | HostAllocFlags::WRITECOMBINED;
+    /// let mut buffer = LockedBuffer::new_with_flags(&0u64, 5, flags).unwrap();
+    /// buffer[0] = 1;
+    /// ```
+    pub fn new_with_flags(value: &T, size: usize, flags: HostAllocFlags) -> CudaResult<Self> {
+        unsafe {
+            let mut uninit = LockedBuffer::uninitialized_with_flags(size, flags)?;
+            for x in 0..size {
+                *uninit.get_unchecked_mut(x) = value.clone();
+            }
+            Ok(uninit)
+        }
+    }
+
+    /// Allocate a new write-combined page-locked buffer large enough to hold `size` `T`'s and
+    /// initialized with clones of `value`.
+    ///
+    /// Write-combined memory is not cached by the host, which frees up the host's caches and lets
+    /// the device read it faster across the PCI Express bus. In exchange, reading from it on the
+    /// host is very slow, so it should only be used for staging buffers which the host fills and
+    /// then uploads to the device. This is equivalent to
+    /// [`new_with_flags`](#method.new_with_flags) with `HostAllocFlags::WRITECOMBINED`.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA. If `size` is large enough that
+    /// `size * mem::sizeof::<T>()` overflows usize, then returns InvalidMemoryAllocation.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut staging = LockedBuffer::new_write_combined(&0u64, 5).unwrap();
+    /// for (i, x) in staging.iter_mut().enumerate() {
+    ///     *x = i as u64;
+    /// }
+    /// let device = DeviceBuffer::from_slice(&staging).unwrap();
+    /// ```
+    pub fn new_write_combined(value: &T, size: usize) -> CudaResult<Self> {
+        LockedBuffer::new_with_flags(value, size, HostAllocFlags::WRITECOMBINED)
+    }
+
     /// Allocate a new page-locked buffer of the same size as `slice`, initialized with a clone of
     /// the data in `slice`.
     ///
@@ -98,7 +159,7 @@ impl<T: DeviceCopy> LockedBuffer<T> {
             .ok_or(CudaError::InvalidMemoryAllocation)?;
 
         let ptr: *mut T = if bytes > 0 {
-            cuda_malloc_locked(bytes)?
+            cuda_malloc_locked(size)?
         } else {
             ptr::NonNull::dangling().as_ptr()
         };
@@ -108,6 +169,46 @@ impl<T: DeviceCopy> LockedBuffer<T> {
         })
     }
 
+    /// Allocate a new page-locked buffer with the given allocation flags, large enough to hold
+    /// `size` `T`'s, but without initializing the contents.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA. If `size` is large enough that
+    /// `size * mem::sizeof::<T>()` overflows usize, then returns InvalidMemoryAllocation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the contents of the buffer are initialized before reading from
+    /// the buffer.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut buffer =
+    ///     unsafe { LockedBuffer::uninitialized_with_flags(5, HostAllocFlags::PORTABLE).unwrap() };
+    /// for i in buffer.iter_mut() {
+    ///     *i = 0u64;
+    /// }
+    /// ```
+    pub unsafe fn uninitialized_with_flags(size: usize, flags: HostAllocFlags) -> CudaResult<Self> {
+        let bytes = size
+            .checked_mul(size_of::<T>())
+            .ok_or(CudaError::InvalidMemoryAllocation)?;
+
+        let ptr: *mut T = if bytes > 0 {
+            cuda_malloc_locked_with_flags(size, flags)?
+        } else {
+            ptr::NonNull::dangling().as_ptr()
+        };
+        Ok(LockedBuffer {
+            buf: ptr,
+            capacity: size,
+        })
+    }
+
     /// Extracts a slice containing the entire buffer.
     ///
     /// Equivalent to `&s[..]`.
@@ -298,6 +399,37 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_new_with_flags() {
+        let _context = crate::quick_init().unwrap();
+        for bits in 0..8 {
+            let flags = HostAllocFlags::from_bits(bits).unwrap();
+            let mut buffer = LockedBuffer::new_with_flags(&0u64, 5, flags).unwrap();
+            buffer[0] = 1;
+            LockedBuffer::drop(buffer).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_write_combined_upload() {
+        use crate::memory::{AsyncCopyDestination, DeviceBuffer};
+        use crate::stream::{Stream, StreamFlags};
+
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let mut staging = LockedBuffer::new_write_combined(&0u32, 1024).unwrap();
+        for (i, x) in staging.iter_mut().enumerate() {
+            *x = i as u32;
+        }
+        let mut device = DeviceBuffer::from_slice(&[0u32; 1024]).unwrap();
+        unsafe {
+            device.async_copy_from(&staging, &stream).unwrap();
+        }
+        stream.synchronize().unwrap();
+        let expected: Vec<u32> = (0..1024).collect();
+        assert_eq!(expected, device.copy_to_vec().unwrap());
+    }
+
     #[test]
     fn from_raw_parts() {
         let _context = crate::quick_init().unwrap();
@@ -326,6 +458,17 @@ mod test {
         drop(buffer);
     }
 
+    #[test]
+    fn large_element_type() {
+        let _context = crate::quick_init().unwrap();
+        // 16 MiB of 64 KiB elements. Over-allocating by a factor of the element size would ask
+        // for 1 TiB of page-locked memory, which fails.
+        let mut buffer =
+            unsafe { LockedBuffer::<[[[u16; 32]; 32]; 32]>::uninitialized(256).unwrap() };
+        buffer[255][31][31][31] = 1;
+        assert_eq!(1, buffer[255][31][31][31]);
+    }
+
     #[test]
     fn overflows_usize() {
         let _context = crate::quick_init().unwrap();
@@ -3,9 +3,14 @@ use crate::error::*;
 use crate::memory::DevicePointer;
 use crate::memory::UnifiedPointer;
 use cuda_sys::cuda;
+#[cfg(all(feature = "track-frees", debug_assertions))]
+use std::collections::BTreeSet;
 use std::mem;
 use std::os::raw::c_void;
 use std::ptr;
+#[cfg(all(feature = "track-frees", debug_assertions))]
+use std::sync::Mutex;
+use std::sync::RwLock;
 
 /// Unsafe wrapper around the `cuMemAlloc` function, which allocates some device memory and
 /// returns a [`DevicePointer`](struct.DevicePointer.html) pointing to it. The memory is not cleared.
@@ -46,6 +51,7 @@ pub unsafe fn cuda_malloc<T>(count: usize) -> CudaResult<DevicePointer<T>> {
 
     let mut ptr: *mut c_void = ptr::null_mut();
     cuda::cuMemAlloc_v2(&mut ptr as *mut *mut c_void as *mut u64, size).to_result()?;
+    track_alloc(ptr as usize);
     let ptr = ptr as *mut T;
     Ok(DevicePointer::wrap(ptr as *mut T))
 }
@@ -96,6 +102,7 @@ pub unsafe fn cuda_malloc_unified<T: DeviceCopy>(count: usize) -> CudaResult<Uni
         cuda::CUmemAttach_flags_enum::CU_MEM_ATTACH_GLOBAL as u32,
     )
     .to_result()?;
+    track_alloc(ptr as usize);
     let ptr = ptr as *mut T;
     Ok(UnifiedPointer::wrap(ptr as *mut T))
 }
@@ -129,8 +136,10 @@ pub unsafe fn cuda_free<T>(mut p: DevicePointer<T>) -> CudaResult<()> {
         return Err(CudaError::InvalidMemoryAllocation);
     }
 
-    cuda::cuMemFree_v2(ptr as u64).to_result()?;
-    Ok(())
+    track_free(ptr as usize);
+    cuda::cuMemFree_v2(ptr as u64)
+        .to_result()
+        .map_err(|e| untrack_free(ptr as usize, e))
 }
 
 /// Free memory allocated with [`cuda_malloc_unified`](fn.cuda_malloc_unified.html).
@@ -162,8 +171,10 @@ pub unsafe fn cuda_free_unified<T: DeviceCopy>(mut p: UnifiedPointer<T>) -> Cuda
         return Err(CudaError::InvalidMemoryAllocation);
     }
 
-    cuda::cuMemFree_v2(ptr as u64).to_result()?;
-    Ok(())
+    track_free(ptr as usize);
+    cuda::cuMemFree_v2(ptr as u64)
+        .to_result()
+        .map_err(|e| untrack_free(ptr as usize, e))
 }
 
 /// Unsafe wrapper around the `cuMemAllocHost` function, which allocates some page-locked host memory
@@ -205,10 +216,81 @@ pub unsafe fn cuda_malloc_locked<T>(count: usize) -> CudaResult<*mut T> {
 
     let mut ptr: *mut c_void = ptr::null_mut();
     cuda::cuMemAllocHost_v2(&mut ptr as *mut *mut c_void, size).to_result()?;
+    track_alloc(ptr as usize);
     let ptr = ptr as *mut T;
     Ok(ptr as *mut T)
 }
 
+bitflags! {
+    /// Bit flags for configuring page-locked host memory allocations.
+    pub struct HostAllocFlags: u32 {
+        /// No flags set. Equivalent to allocating with `cuda_malloc_locked`.
+        const DEFAULT = 0x00;
+
+        /// The memory is considered page-locked by all CUDA contexts, not just the one which
+        /// allocated it.
+        const PORTABLE = 0x01;
+
+        /// Map the allocation into the device address space. The context must have been created
+        /// with `ContextFlags::MAP_HOST` for this flag to take effect.
+        const DEVICEMAP = 0x02;
+
+        /// Allocate the memory as write-combined. Write-combined memory can be transferred across
+        /// the PCI Express bus more quickly and is faster for the host to write, but it is very
+        /// slow for the host to read. It should only be used for buffers that the host writes to
+        /// and the device reads from.
+        const WRITECOMBINED = 0x04;
+    }
+}
+
+/// Unsafe wrapper around the `cuMemHostAlloc` function, which allocates some page-locked host
+/// memory with the given flags and returns a raw pointer pointing to it. The memory is not cleared.
+///
+/// Note that `count` is in units of T; thus a `count` of 3 will allocate `3 * size_of::<T>()` bytes
+/// of memory.
+///
+/// Memory buffers allocated using `cuda_malloc_locked_with_flags` must be freed using
+/// [`cuda_free_locked`](fn.cuda_free_locked.html).
+///
+/// # Errors
+///
+/// If allocating memory fails, returns the CUDA error value.
+/// If the number of bytes to allocate is zero (either because count is zero or because T is a
+/// zero-sized type), or if the size of the allocation would overflow a usize, returns InvalidValue.
+///
+/// # Safety
+///
+/// Since the allocated memory is not initialized, the caller must ensure that it is initialized
+/// before reading from it in any way. Additionally, the caller must ensure that the memory
+/// allocated is freed using `cuda_free_locked`, or the memory will be leaked.
+///
+/// # Examples
+///
+/// ```
+/// # let _context = rustacuda::quick_init().unwrap();
+/// use rustacuda::memory::*;
+/// unsafe {
+///     // Allocate space for 5 u64s in write-combined memory
+///     let locked_buffer =
+///         cuda_malloc_locked_with_flags::<u64>(5, HostAllocFlags::WRITECOMBINED).unwrap();
+///     cuda_free_locked(locked_buffer).unwrap();
+/// }
+/// ```
+pub unsafe fn cuda_malloc_locked_with_flags<T>(
+    count: usize,
+    flags: HostAllocFlags,
+) -> CudaResult<*mut T> {
+    let size = count.checked_mul(size_of::<T>()).unwrap_or(0);
+    if size == 0 {
+        return Err(CudaError::InvalidMemoryAllocation);
+    }
+
+    let mut ptr: *mut c_void = ptr::null_mut();
+    cuda::cuMemHostAlloc(&mut ptr as *mut *mut c_void, size, flags.bits()).to_result()?;
+    track_alloc(ptr as usize);
+    Ok(ptr as *mut T)
+}
+
 /// Free page-locked memory allocated with [`cuda_malloc_host`](fn.cuda_malloc_host.html).
 ///
 /// # Errors
@@ -218,7 +300,8 @@ pub unsafe fn cuda_malloc_locked<T>(count: usize) -> CudaResult<*mut T> {
 ///
 /// # Safety
 ///
-/// The given pointer must have been allocated with `cuda_malloc_locked`, or null.
+/// The given pointer must have been allocated with `cuda_malloc_locked` or
+/// `cuda_malloc_locked_with_flags`, or null.
 /// The caller is responsible for ensuring that no other pointers to the deallocated buffer exist.
 ///
 /// # Examples
@@ -237,8 +320,75 @@ pub unsafe fn cuda_free_locked<T>(ptr: *mut T) -> CudaResult<()> {
         return Err(CudaError::InvalidMemoryAllocation);
     }
 
-    cuda::cuMemFreeHost(ptr as *mut c_void).to_result()?;
-    Ok(())
+    track_free(ptr as usize);
+    cuda::cuMemFreeHost(ptr as *mut c_void)
+        .to_result()
+        .map_err(|e| untrack_free(ptr as usize, e))
+}
+
+// Addresses which have been freed through the `cuda_free*` functions, for detecting double frees
+// in debug builds with the `track-frees` feature. An address is removed again when the driver
+// hands it out for a new allocation.
+#[cfg(all(feature = "track-frees", debug_assertions))]
+static FREED_ADDRESSES: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+// Record that the driver has allocated memory at `addr`.
+#[cfg(all(feature = "track-frees", debug_assertions))]
+pub(crate) fn track_alloc(addr: usize) {
+    let _ = FREED_ADDRESSES.lock().unwrap().remove(&addr);
+}
+#[cfg(not(all(feature = "track-frees", debug_assertions)))]
+#[inline(always)]
+pub(crate) fn track_alloc(_addr: usize) {}
+
+// Record that the memory at `addr` is about to be freed, panicking if it already has been.
+#[cfg(all(feature = "track-frees", debug_assertions))]
+fn track_free(addr: usize) {
+    if !FREED_ADDRESSES.lock().unwrap().insert(addr) {
+        panic!(
+            "Double free: CUDA memory at {:#x} has already been freed",
+            addr
+        );
+    }
+}
+#[cfg(not(all(feature = "track-frees", debug_assertions)))]
+#[inline(always)]
+fn track_free(_addr: usize) {}
+
+// Undo `track_free` when freeing fails, since the memory is still allocated.
+fn untrack_free(addr: usize, error: CudaError) -> CudaError {
+    track_alloc(addr);
+    error
+}
+
+static DROP_ERROR_HANDLER: RwLock<Option<fn(CudaError)>> = RwLock::new(None);
+
+/// Set the handler for errors which occur while freeing memory in a destructor.
+///
+/// Destructors can't return errors, so by default they panic if freeing memory fails. Buffers
+/// which have opted out of that panic (see
+/// [`UnifiedBuffer::set_panic_on_drop_error`](struct.UnifiedBuffer.html#method.set_panic_on_drop_error))
+/// pass the error to this handler instead. If no handler is set, such errors are ignored. Passing
+/// `None` removes the current handler.
+///
+/// The handler is global to the process.
+///
+/// # Examples
+///
+/// ```
+/// use rustacuda::memory::*;
+/// set_drop_error_handler(Some(|e| eprintln!("Failed to free CUDA memory: {}", e)));
+/// ```
+pub fn set_drop_error_handler(handler: Option<fn(CudaError)>) {
+    *DROP_ERROR_HANDLER.write().unwrap() = handler;
+}
+
+// Pass an error from a destructor to the handler set by `set_drop_error_handler`, if any.
+pub(crate) fn handle_drop_error(error: CudaError) {
+    let handler = *DROP_ERROR_HANDLER.read().unwrap();
+    if let Some(handler) = handler {
+        handler(error);
+    }
 }
 
 #[cfg(test)]
@@ -249,6 +399,18 @@ mod test {
     struct ZeroSizedType;
     unsafe impl DeviceCopy for ZeroSizedType {}
 
+    #[cfg(all(feature = "track-frees", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "Double free")]
+    fn test_double_free_is_detected() {
+        let _context = crate::quick_init().unwrap();
+        unsafe {
+            let ptr = cuda_malloc::<u64>(5).unwrap();
+            cuda_free(ptr).unwrap();
+            let _ = cuda_free(ptr);
+        }
+    }
+
     #[test]
     fn test_cuda_malloc() {
         let _context = crate::quick_init().unwrap();
@@ -365,6 +527,23 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_cuda_malloc_locked_with_flags() {
+        let _context = crate::quick_init().unwrap();
+        for bits in 0..8 {
+            let flags = HostAllocFlags::from_bits(bits).unwrap();
+            unsafe {
+                let locked = cuda_malloc_locked_with_flags::<u64>(16, flags).unwrap();
+                assert!(!locked.is_null());
+
+                // Write to the allocated memory
+                *locked = 64;
+
+                cuda_free_locked(locked).unwrap();
+            }
+        }
+    }
+
     #[test]
     fn test_cuda_malloc_locked_zero_bytes() {
         let _context = crate::quick_init().unwrap();
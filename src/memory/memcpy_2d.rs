@@ -0,0 +1,233 @@
+use crate::error::{CudaError, CudaResult, ToResult};
+use crate::memory::{DeviceCopy, DeviceSlice};
+use cuda_sys::cuda::{self, CUdeviceptr, CUmemorytype, CUDA_MEMCPY2D};
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+use std::ptr;
+
+/// Builder for copying a rectangular region between host and device memory with `cuMemcpy2D`.
+///
+/// A two-dimensional copy transfers `height` rows of `width_in_bytes` bytes each. The source and
+/// destination each have their own pitch (the distance in bytes between the starts of two
+/// consecutive rows) and may start at an offset, so a sub-rectangle of a larger array can be
+/// copied into a packed buffer or vice versa. Host-to-device, device-to-host, device-to-device
+/// and host-to-host copies are supported.
+///
+/// The source and destination are given as slices, and the rows described by the offsets,
+/// pitches and size must lie within them, so the copy cannot read or write out of bounds.
+///
+/// # Examples:
+///
+/// Copy the interior 2x2 of a 4x4 host matrix into a packed device buffer:
+///
+/// ```
+/// # let _context = rustacuda::quick_init().unwrap();
+/// use rustacuda::memory::*;
+/// use std::mem::size_of;
+/// let matrix: Vec<f32> = (0..16).map(|x| x as f32).collect();
+/// let mut interior = DeviceBuffer::from_slice(&[0.0f32; 4]).unwrap();
+///
+/// Memcpy2D::new(2 * size_of::<f32>(), 2)
+///     .src_host(&matrix, 4 * size_of::<f32>())
+///     .src_offset(size_of::<f32>(), 1)
+///     .dst_device(&mut interior, 2 * size_of::<f32>())
+///     .copy()
+///     .unwrap();
+///
+/// let mut host = [0.0f32; 4];
+/// interior.copy_to(&mut host).unwrap();
+/// assert_eq!([5.0, 6.0, 9.0, 10.0], host);
+/// ```
+#[derive(Debug)]
+pub struct Memcpy2D<'a> {
+    copy: CUDA_MEMCPY2D,
+    src_bytes: Option<usize>,
+    dst_bytes: Option<usize>,
+    _borrows: PhantomData<&'a mut ()>,
+}
+impl<'a> Memcpy2D<'a> {
+    /// Start describing a copy of `height` rows of `width_in_bytes` bytes each.
+    pub fn new(width_in_bytes: usize, height: usize) -> Self {
+        let mut copy = empty_copy_2d();
+        copy.WidthInBytes = width_in_bytes;
+        copy.Height = height;
+        Memcpy2D {
+            copy,
+            src_bytes: None,
+            dst_bytes: None,
+            _borrows: PhantomData,
+        }
+    }
+
+    /// Copy from host memory, with rows `pitch` bytes apart.
+    pub fn src_host<T: DeviceCopy>(mut self, src: &'a [T], pitch: usize) -> Self {
+        self.copy.srcMemoryType = CUmemorytype::CU_MEMORYTYPE_HOST;
+        self.copy.srcHost = src.as_ptr() as *const c_void;
+        self.copy.srcPitch = pitch;
+        self.src_bytes = Some(size_of_val(src));
+        self
+    }
+
+    /// Copy from device memory, with rows `pitch` bytes apart.
+    pub fn src_device<T: DeviceCopy>(mut self, src: &'a DeviceSlice<T>, pitch: usize) -> Self {
+        self.copy.srcMemoryType = CUmemorytype::CU_MEMORYTYPE_DEVICE;
+        self.copy.srcDevice = src.as_ptr() as CUdeviceptr;
+        self.copy.srcPitch = pitch;
+        self.src_bytes = Some(src.len() * size_of::<T>());
+        self
+    }
+
+    /// Start copying from `x_in_bytes` bytes into row `y` of the source, rather than from the
+    /// start of the first row.
+    pub fn src_offset(mut self, x_in_bytes: usize, y: usize) -> Self {
+        self.copy.srcXInBytes = x_in_bytes;
+        self.copy.srcY = y;
+        self
+    }
+
+    /// Copy into host memory, with rows `pitch` bytes apart.
+    pub fn dst_host<T: DeviceCopy>(mut self, dst: &'a mut [T], pitch: usize) -> Self {
+        self.copy.dstMemoryType = CUmemorytype::CU_MEMORYTYPE_HOST;
+        self.copy.dstHost = dst.as_mut_ptr() as *mut c_void;
+        self.copy.dstPitch = pitch;
+        self.dst_bytes = Some(size_of_val(dst));
+        self
+    }
+
+    /// Copy into device memory, with rows `pitch` bytes apart.
+    pub fn dst_device<T: DeviceCopy>(mut self, dst: &'a mut DeviceSlice<T>, pitch: usize) -> Self {
+        self.copy.dstMemoryType = CUmemorytype::CU_MEMORYTYPE_DEVICE;
+        self.copy.dstDevice = dst.as_mut_ptr() as CUdeviceptr;
+        self.copy.dstPitch = pitch;
+        self.dst_bytes = Some(dst.len() * size_of::<T>());
+        self
+    }
+
+    /// Start copying to `x_in_bytes` bytes into row `y` of the destination, rather than to the
+    /// start of the first row.
+    pub fn dst_offset(mut self, x_in_bytes: usize, y: usize) -> Self {
+        self.copy.dstXInBytes = x_in_bytes;
+        self.copy.dstY = y;
+        self
+    }
+
+    /// Perform the copy. This blocks until the copy has completed.
+    ///
+    /// # Errors:
+    ///
+    /// Returns `InvalidValue` if the source or destination has not been set, if either pitch is
+    /// smaller than the width, or if the rows to copy do not lie entirely within the source and
+    /// destination slices. If the copy fails, returns the error from CUDA.
+    pub fn copy(&self) -> CudaResult<()> {
+        let copy = &self.copy;
+        let (src_bytes, dst_bytes) = match (self.src_bytes, self.dst_bytes) {
+            (Some(src), Some(dst)) => (src, dst),
+            _ => return Err(CudaError::InvalidValue),
+        };
+        if copy.WidthInBytes == 0 || copy.Height == 0 {
+            return Ok(());
+        }
+        let fits = |x: usize, y: usize, pitch: usize, len: usize| {
+            // One past the last byte copied, or None if that overflows.
+            let end = y
+                .checked_add(copy.Height - 1)
+                .and_then(|last_row| last_row.checked_mul(pitch))
+                .and_then(|start| start.checked_add(x))
+                .and_then(|start| start.checked_add(copy.WidthInBytes));
+            match end {
+                Some(end) => pitch >= copy.WidthInBytes && end <= len,
+                None => false,
+            }
+        };
+        if !fits(copy.srcXInBytes, copy.srcY, copy.srcPitch, src_bytes)
+            || !fits(copy.dstXInBytes, copy.dstY, copy.dstPitch, dst_bytes)
+        {
+            return Err(CudaError::InvalidValue);
+        }
+        unsafe { cuda::cuMemcpy2D_v2(copy as *const CUDA_MEMCPY2D).to_result() }
+    }
+}
+
+// A copy descriptor with every field zeroed or null, to be filled in by the caller.
+pub(crate) fn empty_copy_2d() -> CUDA_MEMCPY2D {
+    CUDA_MEMCPY2D {
+        srcXInBytes: 0,
+        srcY: 0,
+        srcMemoryType: CUmemorytype::CU_MEMORYTYPE_HOST,
+        srcHost: ptr::null(),
+        srcDevice: 0,
+        srcArray: ptr::null_mut(),
+        srcPitch: 0,
+        dstXInBytes: 0,
+        dstY: 0,
+        dstMemoryType: CUmemorytype::CU_MEMORYTYPE_HOST,
+        dstHost: ptr::null_mut(),
+        dstDevice: 0,
+        dstArray: ptr::null_mut(),
+        dstPitch: 0,
+        WidthInBytes: 0,
+        Height: 0,
+    }
+}
+
+#[cfg(test)]
+mod test_memcpy_2d {
+    use super::*;
+    use crate::memory::{CopyDestination, DeviceBuffer};
+
+    const F: usize = size_of::<f32>();
+
+    #[test]
+    fn test_copy_interior_to_device() {
+        let _context = crate::quick_init().unwrap();
+        let matrix: Vec<f32> = (0..16).map(|x| x as f32).collect();
+        let mut interior = DeviceBuffer::from_slice(&[0.0f32; 4]).unwrap();
+        Memcpy2D::new(2 * F, 2)
+            .src_host(&matrix, 4 * F)
+            .src_offset(F, 1)
+            .dst_device(&mut interior, 2 * F)
+            .copy()
+            .unwrap();
+
+        let mut host = [0.0f32; 4];
+        interior.copy_to(&mut host).unwrap();
+        assert_eq!([5.0, 6.0, 9.0, 10.0], host);
+    }
+
+    #[test]
+    fn test_copy_device_to_host_and_device() {
+        let _context = crate::quick_init().unwrap();
+        let packed = DeviceBuffer::from_slice(&[1.0f32, 2.0, 3.0, 4.0]).unwrap();
+        let mut device = DeviceBuffer::from_slice(&[0.0f32; 4]).unwrap();
+        Memcpy2D::new(2 * F, 2)
+            .src_device(&packed, 2 * F)
+            .dst_device(&mut device, 2 * F)
+            .copy()
+            .unwrap();
+
+        let mut matrix = [0.0f32; 9];
+        Memcpy2D::new(2 * F, 2)
+            .src_device(&device, 2 * F)
+            .dst_host(&mut matrix, 3 * F)
+            .dst_offset(F, 1)
+            .copy()
+            .unwrap();
+        assert_eq!([0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 0.0, 3.0, 4.0], matrix);
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let _context = crate::quick_init().unwrap();
+        let matrix = [0.0f32; 16];
+        let mut packed = DeviceBuffer::from_slice(&[0.0f32; 4]).unwrap();
+        let result = Memcpy2D::new(2 * F, 2)
+            .src_host(&matrix, 4 * F)
+            .src_offset(3 * F, 3)
+            .dst_device(&mut packed, 2 * F)
+            .copy();
+        assert_eq!(Err(CudaError::InvalidValue), result);
+
+        let result = Memcpy2D::new(2 * F, 2).src_host(&matrix, 4 * F).copy();
+        assert_eq!(Err(CudaError::InvalidValue), result);
+    }
+}
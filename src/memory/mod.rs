@@ -17,8 +17,8 @@
 //! Unified memory is a memory allocation which can be read from and written to by both the host
 //! and the device. When the host (or device) attempts to access a page of unified memory, it is
 //! seamlessly transferred from host RAM to device RAM or vice versa. The programmer may also
-//! choose to explicitly prefetch data to one side or another (though this is not currently exposed
-//! through RustaCUDA). RustaCUDA exposes unified memory through the
+//! choose to explicitly prefetch data to one side or another (see
+//! `UnifiedBuffer::prefetch_to_host_after`). RustaCUDA exposes unified memory through the
 //! [`UnifiedBox`](struct.UnifiedBox.html) and [`UnifiedBuffer`](struct.UnifiedBuffer.html)
 //! structures, and pointers to unified memory are represented by
 //! [`UnifiedPointer`](struct.UnifiedPointer.html). Since unified memory is accessible to the host,
@@ -38,7 +38,8 @@
 //! the driver copies the data into page-locked host memory then into the normal memory space. This
 //! extra copy can be eliminated if the data is loaded or generated directly into page-locked
 //! memory. RustaCUDA exposes page-locked memory through the
-//! [`LockedBuffer`](struct.LockedBuffer.html) struct.
+//! [`LockedBuffer`](struct.LockedBuffer.html) struct. Existing `Vec`s can also be page-locked in
+//! place by wrapping them in a [`PinnedVec`](struct.PinnedVec.html).
 //!
 //! For example, if the programmer needs to read an array of bytes from disk and transfer it to the
 //! device, it would be best to create a `LockedBuffer`, load the bytes directly into the
@@ -67,13 +68,21 @@
 
 pub mod array;
 
+mod cast;
+mod checked;
 mod device;
 mod locked;
 mod malloc;
+mod memcpy_2d;
+mod pinned;
 mod unified;
 
+pub use self::cast::*;
+pub use self::checked::*;
 pub use self::device::*;
 pub use self::locked::*;
 pub use self::malloc::*;
+pub use self::memcpy_2d::*;
+pub use self::pinned::*;
 pub use self::unified::*;
-pub use rustacuda_core::{DeviceCopy, DevicePointer, UnifiedPointer};
+pub use rustacuda_core::{DeviceCopy, DevicePointer, UnifiedPointer, Zeroable};
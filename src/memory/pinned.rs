@@ -0,0 +1,446 @@
+use super::DeviceCopy;
+use crate::error::*;
+use cuda_sys::cuda;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops;
+use std::os::raw::c_void;
+use std::slice;
+
+/// Fixed-capacity `Vec` whose buffer is page-locked for as long as the `PinnedVec` exists.
+///
+/// Unlike [`LockedBuffer`](struct.LockedBuffer.html), which allocates new page-locked memory, a
+/// `PinnedVec` takes an existing `Vec` and registers its buffer with the driver using
+/// `cuMemHostRegister`. This avoids copying data which was already loaded into a `Vec`. The buffer
+/// is unregistered when the `PinnedVec` is dropped or converted back with
+/// [`into_vec`](#method.into_vec).
+///
+/// The whole capacity of the `Vec` is registered, so elements can be pushed and popped as long as
+/// the length stays within that capacity. A `PinnedVec` can never grow beyond its capacity, since
+/// reallocating the buffer would leave the old allocation registered and the new one pageable.
+/// Reserve enough capacity before creating the `PinnedVec`, or use
+/// [`with_capacity`](#method.with_capacity).
+///
+/// See the [`module-level documentation`](../memory/index.html) for more details on page-locked
+/// memory.
+#[derive(Debug)]
+pub struct PinnedVec<T: DeviceCopy> {
+    vec: Vec<T>,
+}
+impl<T: DeviceCopy> PinnedVec<T> {
+    /// Page-lock the buffer of `vec`, including any spare capacity.
+    ///
+    /// # Errors:
+    ///
+    /// If registering the buffer fails, returns the error from CUDA along with `vec`, unchanged.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut vec = Vec::with_capacity(10);
+    /// vec.extend_from_slice(&[1u32, 2, 3]);
+    /// let pinned = PinnedVec::new(vec).map_err(|(e, _)| e).unwrap();
+    /// assert_eq!(&[1, 2, 3], pinned.as_slice());
+    /// assert_eq!(10, pinned.capacity());
+    /// ```
+    pub fn new(mut vec: Vec<T>) -> Result<Self, (CudaError, Vec<T>)> {
+        let bytes = vec.capacity() * size_of::<T>();
+        if bytes > 0 {
+            let result = unsafe {
+                cuda::cuMemHostRegister_v2(vec.as_mut_ptr() as *mut c_void, bytes, 0).to_result()
+            };
+            if let Err(e) = result {
+                return Err((e, vec));
+            }
+        }
+        Ok(PinnedVec { vec })
+    }
+
+    /// Create a new, empty `PinnedVec` which can hold up to `capacity` elements.
+    ///
+    /// # Errors:
+    ///
+    /// If registering the buffer fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut pinned = PinnedVec::with_capacity(4).unwrap();
+    /// pinned.push(1u64).unwrap();
+    /// assert_eq!(1, pinned.len());
+    /// ```
+    pub fn with_capacity(capacity: usize) -> CudaResult<Self> {
+        PinnedVec::new(Vec::with_capacity(capacity)).map_err(|(e, _)| e)
+    }
+
+    /// Returns the number of elements the `PinnedVec` can hold.
+    ///
+    /// This never changes after the `PinnedVec` is created.
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Append an element to the back of the `PinnedVec`.
+    ///
+    /// # Errors:
+    ///
+    /// If the `PinnedVec` is full, returns `value` without modifying the `PinnedVec`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut pinned = PinnedVec::with_capacity(1).unwrap();
+    /// assert_eq!(Ok(()), pinned.push(1u32));
+    /// assert_eq!(Err(2), pinned.push(2u32));
+    /// ```
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.vec.len() == self.vec.capacity() {
+            return Err(value);
+        }
+        self.vec.push(value);
+        Ok(())
+    }
+
+    /// Remove the last element of the `PinnedVec` and return it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.vec.pop()
+    }
+
+    /// Shorten the `PinnedVec` to `len` elements. Has no effect if it is already shorter.
+    ///
+    /// The capacity is unchanged, so the buffer remains page-locked.
+    pub fn truncate(&mut self, len: usize) {
+        self.vec.truncate(len);
+    }
+
+    /// Remove all elements from the `PinnedVec`, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+
+    /// Extracts a slice containing the elements of the `PinnedVec`.
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// Extracts a mutable slice containing the elements of the `PinnedVec`.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    /// Unregister the buffer and return the underlying `Vec`.
+    ///
+    /// # Errors:
+    ///
+    /// Unregistering the buffer can return errors from previous asynchronous work. On failure,
+    /// returns the error and the still-registered `PinnedVec`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let pinned = PinnedVec::new(vec![1u8, 2, 3]).map_err(|(e, _)| e).unwrap();
+    /// let vec = pinned.into_vec().map_err(|(e, _)| e).unwrap();
+    /// assert_eq!(vec![1u8, 2, 3], vec);
+    /// ```
+    pub fn into_vec(mut self) -> Result<Vec<T>, (CudaError, PinnedVec<T>)> {
+        match unsafe { self.unregister() } {
+            Ok(()) => Ok(mem::take(&mut self.vec)),
+            Err(e) => Err((e, self)),
+        }
+    }
+
+    unsafe fn unregister(&mut self) -> CudaResult<()> {
+        if self.vec.capacity() * size_of::<T>() > 0 {
+            cuda::cuMemHostUnregister(self.vec.as_mut_ptr() as *mut c_void).to_result()?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: DeviceCopy> AsRef<[T]> for PinnedVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+impl<T: DeviceCopy> AsMut<[T]> for PinnedVec<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+impl<T: DeviceCopy> ops::Deref for PinnedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.vec
+    }
+}
+impl<T: DeviceCopy> ops::DerefMut for PinnedVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.vec
+    }
+}
+impl<T: DeviceCopy> Drop for PinnedVec<T> {
+    fn drop(&mut self) {
+        // No choice but to panic if this fails.
+        unsafe {
+            self.unregister()
+                .expect("Failed to unregister CUDA page-locked memory.");
+        }
+    }
+}
+
+bitflags! {
+    /// Bit flags for page-locking existing host memory with
+    /// [`HostRegistered`](struct.HostRegistered.html).
+    pub struct HostRegisterFlags: u32 {
+        /// No flags set.
+        const DEFAULT = 0x00;
+
+        /// The memory is considered page-locked by all CUDA contexts, not just the current one.
+        const PORTABLE = 0x01;
+
+        /// Map the memory into the device address space. The context must have been created with
+        /// `ContextFlags::MAP_HOST` for this flag to take effect.
+        const DEVICEMAP = 0x02;
+
+        /// The memory will only be read by the device, never written. This requires a CUDA 11.1
+        /// or newer driver; older drivers reject the registration with `InvalidValue`.
+        const READ_ONLY = 0x08;
+    }
+}
+
+/// A borrowed host slice which is page-locked for as long as the `HostRegistered` exists.
+///
+/// Like [`PinnedVec`](struct.PinnedVec.html), this registers existing memory with the driver using
+/// `cuMemHostRegister` instead of allocating new page-locked memory, but it borrows any mutable
+/// slice rather than taking ownership of a `Vec`. The registration borrows the slice, so it can't
+/// outlive the data, and the memory is unregistered when the `HostRegistered` is dropped.
+///
+/// A `HostRegistered` can be used as the source or destination of asynchronous copies. As with
+/// all asynchronous copies, the caller must ensure that the copies have completed before the
+/// `HostRegistered` is dropped.
+///
+/// See the [`module-level documentation`](../memory/index.html) for more details on page-locked
+/// memory.
+///
+/// # Examples:
+///
+/// ```
+/// # let _context = rustacuda::quick_init().unwrap();
+/// use rustacuda::memory::*;
+/// use rustacuda::stream::{Stream, StreamFlags};
+/// let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+/// let mut data = vec![1u32, 2, 3];
+/// let mut device = DeviceBuffer::from_slice(&[0u32; 3]).unwrap();
+/// {
+///     let registered = HostRegistered::new(&mut data, HostRegisterFlags::DEFAULT).unwrap();
+///     unsafe { device.async_copy_from(&registered, &stream).unwrap() };
+///     stream.synchronize().unwrap();
+/// }
+/// assert_eq!(vec![1, 2, 3], device.copy_to_vec().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct HostRegistered<'a, T: DeviceCopy> {
+    ptr: *mut T,
+    len: usize,
+    _slice: PhantomData<&'a mut [T]>,
+}
+impl<'a, T: DeviceCopy> HostRegistered<'a, T> {
+    /// Page-lock the memory of `slice` until the returned `HostRegistered` is dropped.
+    ///
+    /// Empty slices and slices of zero-sized types are not registered with the driver.
+    ///
+    /// # Errors:
+    ///
+    /// If registering the memory fails, returns the error from CUDA. Registering memory which is
+    /// already page-locked, or which overlaps another registration, fails with
+    /// `HostMemoryAlreadyRegistered`.
+    pub fn new(slice: &'a mut [T], flags: HostRegisterFlags) -> CudaResult<Self> {
+        let bytes = size_of_val(slice);
+        if bytes > 0 {
+            unsafe {
+                cuda::cuMemHostRegister_v2(slice.as_mut_ptr() as *mut c_void, bytes, flags.bits())
+                    .to_result()?;
+            }
+        }
+        Ok(HostRegistered {
+            ptr: slice.as_mut_ptr(),
+            len: slice.len(),
+            _slice: PhantomData,
+        })
+    }
+
+    /// Unregister the memory, returning an error.
+    ///
+    /// Unregistering memory can return errors from previous asynchronous work. This function
+    /// unregisters the memory and returns the error and the still-registered `HostRegistered` on
+    /// failure.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut data = [0u8; 16];
+    /// let registered = HostRegistered::new(&mut data, HostRegisterFlags::PORTABLE).unwrap();
+    /// match HostRegistered::drop(registered) {
+    ///     Ok(()) => println!("Successfully unregistered"),
+    ///     Err((e, registered)) => {
+    ///         println!("Failed to unregister memory: {:?}", e);
+    ///         // Do something with registered
+    ///     },
+    /// };
+    /// ```
+    pub fn drop(mut registered: HostRegistered<'a, T>) -> DropResult<HostRegistered<'a, T>> {
+        match unsafe { registered.unregister() } {
+            Ok(()) => {
+                mem::forget(registered);
+                Ok(())
+            }
+            Err(e) => Err((e, registered)),
+        }
+    }
+
+    unsafe fn unregister(&mut self) -> CudaResult<()> {
+        if self.len * size_of::<T>() > 0 {
+            cuda::cuMemHostUnregister(self.ptr as *mut c_void).to_result()?;
+        }
+        Ok(())
+    }
+}
+impl<'a, T: DeviceCopy> AsRef<[T]> for HostRegistered<'a, T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+impl<'a, T: DeviceCopy> AsMut<[T]> for HostRegistered<'a, T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+impl<'a, T: DeviceCopy> ops::Deref for HostRegistered<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+impl<'a, T: DeviceCopy> ops::DerefMut for HostRegistered<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+impl<'a, T: DeviceCopy> Drop for HostRegistered<'a, T> {
+    fn drop(&mut self) {
+        // No choice but to panic if this fails.
+        unsafe {
+            self.unregister()
+                .expect("Failed to unregister CUDA page-locked memory.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_pinned {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug)]
+    struct ZeroSizedType;
+    unsafe impl DeviceCopy for ZeroSizedType {}
+
+    #[test]
+    fn test_push_within_capacity() {
+        let _context = crate::quick_init().unwrap();
+        let mut pinned = PinnedVec::with_capacity(3).unwrap();
+        for i in 0..3u32 {
+            pinned.push(i).unwrap();
+        }
+        assert_eq!(Err(3), pinned.push(3));
+        assert_eq!(&[0, 1, 2], pinned.as_slice());
+        assert_eq!(Some(2), pinned.pop());
+        pinned.push(5).unwrap();
+        assert_eq!(&[0, 1, 5], pinned.as_slice());
+        assert_eq!(3, pinned.capacity());
+    }
+
+    #[test]
+    fn test_into_vec() {
+        let _context = crate::quick_init().unwrap();
+        let pinned = PinnedVec::new(vec![1u64, 2, 3]).unwrap();
+        let mut vec = pinned.into_vec().map_err(|(e, _)| e).unwrap();
+        // The buffer is no longer registered, so it can grow again.
+        vec.push(4);
+        assert_eq!(vec![1u64, 2, 3, 4], vec);
+    }
+
+    #[test]
+    fn test_new_failure_returns_vec() {
+        use std::thread;
+
+        crate::init(crate::CudaFlags::empty()).unwrap();
+        // No context is current on a new thread, so registering the buffer fails.
+        let (err, vec) = thread::spawn(|| PinnedVec::new(vec![1u32, 2, 3]).unwrap_err())
+            .join()
+            .unwrap();
+        assert_eq!(CudaError::InvalidContext, err);
+        assert_eq!(vec![1u32, 2, 3], vec);
+    }
+
+    #[test]
+    fn test_async_copy_source() {
+        use crate::memory::{AsyncCopyDestination, DeviceBuffer};
+        use crate::stream::{Stream, StreamFlags};
+
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let source = PinnedVec::new((0..1024u32).collect()).unwrap();
+        let mut device = DeviceBuffer::from_slice(&[0u32; 1024]).unwrap();
+        let mut result = PinnedVec::new(vec![0u32; 1024]).unwrap();
+        unsafe {
+            device.async_copy_from(&source, &stream).unwrap();
+            device.async_copy_to(&mut result, &stream).unwrap();
+        }
+        stream.synchronize().unwrap();
+        assert_eq!(source.as_slice(), result.as_slice());
+    }
+
+    #[test]
+    fn test_host_registered_async_copies() {
+        use crate::memory::{AsyncCopyDestination, DeviceBuffer};
+        use crate::stream::{Stream, StreamFlags};
+
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let mut source: Vec<u32> = (0..1024).collect();
+        let mut result = vec![0u32; 1024];
+        let mut device = DeviceBuffer::from_slice(&[0u32; 1024]).unwrap();
+        {
+            let source = HostRegistered::new(&mut source, HostRegisterFlags::PORTABLE).unwrap();
+            let mut result = HostRegistered::new(&mut result, HostRegisterFlags::DEFAULT).unwrap();
+            unsafe {
+                device.async_copy_from(&source, &stream).unwrap();
+                device.async_copy_to(&mut result, &stream).unwrap();
+            }
+            stream.synchronize().unwrap();
+            HostRegistered::drop(source).unwrap();
+        }
+        assert_eq!(source, result);
+    }
+
+    #[test]
+    fn test_empty_and_zero_sized() {
+        let _context = crate::quick_init().unwrap();
+        drop(PinnedVec::<u32>::new(Vec::new()).unwrap());
+        drop(PinnedVec::new(vec![ZeroSizedType; 10]).unwrap());
+        drop(HostRegistered::new(&mut [0u32; 0], HostRegisterFlags::DEFAULT).unwrap());
+        drop(HostRegistered::new(&mut [ZeroSizedType; 10], HostRegisterFlags::DEFAULT).unwrap());
+    }
+}
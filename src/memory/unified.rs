@@ -1,9 +1,14 @@
 use super::DeviceCopy;
+use crate::context::CurrentContext;
+use crate::device::{Device, DeviceAttribute};
 use crate::error::*;
-use crate::memory::malloc::{cuda_free_unified, cuda_malloc_unified};
+use crate::event::Event;
+use crate::memory::malloc::{cuda_free_unified, cuda_malloc_unified, handle_drop_error};
 use crate::memory::UnifiedPointer;
+use crate::stream::Stream;
+use cuda_sys::cuda::{self, CUdevice};
 use std::borrow::{Borrow, BorrowMut};
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
 use std::convert::{AsMut, AsRef};
 use std::fmt::{self, Display, Pointer};
 use std::hash::{Hash, Hasher};
@@ -12,6 +17,52 @@ use std::ops::{Deref, DerefMut};
 use std::ptr;
 use std::slice;
 
+// Device ordinal used by the driver to refer to host memory; not exported by cuda-sys.
+const CU_DEVICE_CPU: CUdevice = -1;
+
+/// Hints about how a range of unified memory will be used, for
+/// [`UnifiedBuffer::advise`](struct.UnifiedBuffer.html#method.advise) and
+/// [`UnifiedBox::advise`](struct.UnifiedBox.html#method.advise).
+///
+/// These are only hints to the driver's page migration. They never affect correctness, and the
+/// driver may ignore them.
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum MemAdvise {
+    /// The memory will mostly be read. The driver may keep read-only copies on each processor
+    /// which reads it; writing to the memory invalidates all copies except the writer's.
+    SetReadMostly = 1,
+    /// Undo the effect of `SetReadMostly`.
+    UnsetReadMostly = 2,
+    /// Prefer to keep the memory resident on the given device.
+    SetPreferredLocation = 3,
+    /// Clear the preferred location.
+    UnsetPreferredLocation = 4,
+    /// The memory will be accessed by the given device, so keep it mapped in that device's page
+    /// tables to avoid faults.
+    SetAccessedBy = 5,
+    /// Undo the effect of `SetAccessedBy` for the given device.
+    UnsetAccessedBy = 6,
+}
+
+unsafe fn mem_advise(ptr: u64, bytes: usize, advice: MemAdvise, device: Device) -> CudaResult<()> {
+    let advice = match advice {
+        MemAdvise::SetReadMostly => cuda::CUmem_advise::CU_MEM_ADVISE_SET_READ_MOSTLY,
+        MemAdvise::UnsetReadMostly => cuda::CUmem_advise::CU_MEM_ADVISE_UNSET_READ_MOSTLY,
+        MemAdvise::SetPreferredLocation => cuda::CUmem_advise::CU_MEM_ADVISE_SET_PREFERRED_LOCATION,
+        MemAdvise::UnsetPreferredLocation => {
+            cuda::CUmem_advise::CU_MEM_ADVISE_UNSET_PREFERRED_LOCATION
+        }
+        MemAdvise::SetAccessedBy => cuda::CUmem_advise::CU_MEM_ADVISE_SET_ACCESSED_BY,
+        MemAdvise::UnsetAccessedBy => cuda::CUmem_advise::CU_MEM_ADVISE_UNSET_ACCESSED_BY,
+    };
+    if bytes == 0 {
+        return Ok(());
+    }
+    cuda::cuMemAdvise(ptr, bytes, advice, device.into_inner()).to_result()
+}
+
 /// A pointer type for heap-allocation in CUDA unified memory.
 ///
 /// See the [`module-level documentation`](../memory/index.html) for more information on unified
@@ -179,6 +230,33 @@ impl<T: DeviceCopy> UnifiedBox<T> {
         self.ptr
     }
 
+    /// Give the driver a hint about how this box's memory will be used by `device`.
+    ///
+    /// See [`MemAdvise`](enum.MemAdvise.html) for the available hints. These are only hints,
+    /// which the driver may ignore.
+    ///
+    /// # Errors:
+    ///
+    /// If the device does not support the advice, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::context::CurrentContext;
+    /// use rustacuda::memory::*;
+    /// let x = UnifiedBox::new(5u64)?;
+    /// x.advise(MemAdvise::SetReadMostly, CurrentContext::get_device()?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn advise(&self, advice: MemAdvise, device: Device) -> CudaResult<()> {
+        unsafe { mem_advise(self.ptr.as_raw() as u64, size_of::<T>(), advice, device) }
+    }
+
     /// Consumes and leaks the UnifiedBox, returning a mutable reference, &'a mut T. Note that the type T
     /// must outlive the chosen lifetime 'a. If the type has only static references, or none at all,
     /// this may be chosen to be 'static.
@@ -329,6 +407,7 @@ impl<T: DeviceCopy + Hash> Hash for UnifiedBox<T> {
 pub struct UnifiedBuffer<T: DeviceCopy> {
     buf: UnifiedPointer<T>,
     capacity: usize,
+    panic_on_drop_error: bool,
 }
 impl<T: DeviceCopy + Clone> UnifiedBuffer<T> {
     /// Allocate a new unified buffer large enough to hold `size` `T`'s and initialized with
@@ -420,6 +499,7 @@ impl<T: DeviceCopy> UnifiedBuffer<T> {
         Ok(UnifiedBuffer {
             buf: ptr,
             capacity: size,
+            panic_on_drop_error: true,
         })
     }
 
@@ -469,6 +549,221 @@ impl<T: DeviceCopy> UnifiedBuffer<T> {
         self.buf
     }
 
+    /// Queue a prefetch of this buffer to host memory, to be performed once `wait_on` completes.
+    ///
+    /// This makes `stream` wait on `wait_on` (typically an event recorded after the kernel which
+    /// writes to this buffer) and then migrates the buffer's pages to the host as part of the
+    /// stream. Once the stream has been synchronized, the host can read the buffer without
+    /// triggering page faults.
+    ///
+    /// # Errors:
+    ///
+    /// If queueing the wait or the prefetch fails, returns the error from CUDA. Prefetching is not
+    /// supported on devices without concurrent managed memory access.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::event::{Event, EventFlags};
+    /// use rustacuda::memory::*;
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    ///
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let kernel_done = Event::new(EventFlags::DISABLE_TIMING)?;
+    /// let buffer = UnifiedBuffer::new(&0u64, 5)?;
+    ///
+    /// // ... launch a kernel writing to the buffer, then record the event
+    /// kernel_done.record(&stream)?;
+    ///
+    /// buffer.prefetch_to_host_after(&stream, &kernel_done)?;
+    /// stream.synchronize()?;
+    /// let sum: u64 = buffer.iter().sum();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prefetch_to_host_after(&self, stream: &Stream, wait_on: &Event) -> CudaResult<()> {
+        stream.wait_event(wait_on)?;
+        self.prefetch_to_host(stream)
+    }
+
+    /// Give the driver a hint about how this buffer's memory will be used by `device`.
+    ///
+    /// For example, a buffer written by the host and only read by kernels could be marked
+    /// `SetReadMostly` so each device keeps its own copy, or a buffer produced by one device and
+    /// consumed by another could have its preferred location set to the producer and be marked
+    /// `SetAccessedBy` the consumer. See [`MemAdvise`](enum.MemAdvise.html) for the available
+    /// hints. These are only hints, which the driver may ignore.
+    ///
+    /// # Errors:
+    ///
+    /// If the device does not support the advice, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::context::CurrentContext;
+    /// use rustacuda::memory::*;
+    /// let buffer = UnifiedBuffer::new(&0u64, 1024)?;
+    /// buffer.advise(MemAdvise::SetReadMostly, CurrentContext::get_device()?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn advise(&self, advice: MemAdvise, device: Device) -> CudaResult<()> {
+        unsafe {
+            mem_advise(
+                self.buf.as_raw() as u64,
+                self.capacity * size_of::<T>(),
+                advice,
+                device,
+            )
+        }
+    }
+
+    /// Queue a migration of this buffer's pages to `device` on `stream`.
+    ///
+    /// Without prefetching, a kernel which touches unified memory resident on the host triggers a
+    /// page fault for each page it accesses, and the pages are migrated one by one. This can make
+    /// the kernel many times slower. Prefetching the whole buffer before launching the kernel
+    /// migrates the pages in bulk, as part of the stream, so the kernel runs at full speed.
+    ///
+    /// # Errors:
+    ///
+    /// Returns `NotSupported` if `device` does not support concurrent managed memory access, since
+    /// such devices can't prefetch. If queueing the prefetch fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::context::CurrentContext;
+    /// use rustacuda::memory::*;
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    ///
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let buffer = UnifiedBuffer::new(&0u64, 5)?;
+    /// let device = CurrentContext::get_device()?;
+    /// match buffer.prefetch_to_device(device, &stream) {
+    ///     Ok(()) => { /* ... launch a kernel using the buffer */ }
+    ///     Err(error::CudaError::NotSupported) => println!("Prefetching is not supported"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prefetch_to_device(&self, device: Device, stream: &Stream) -> CudaResult<()> {
+        self.prefetch(device, device.into_inner(), stream)
+    }
+
+    /// Queue a migration of this buffer's pages to host memory on `stream`.
+    ///
+    /// Once the stream has been synchronized, the host can read the buffer without triggering page
+    /// faults. See [`prefetch_to_device`](#method.prefetch_to_device) for more details.
+    ///
+    /// # Errors:
+    ///
+    /// Returns `NotSupported` if the current context's device does not support concurrent managed
+    /// memory access. If queueing the prefetch fails, returns the error from CUDA.
+    pub fn prefetch_to_host(&self, stream: &Stream) -> CudaResult<()> {
+        self.prefetch(CurrentContext::get_device()?, CU_DEVICE_CPU, stream)
+    }
+
+    /// Associate this buffer with `stream`, so that only work queued on `stream` may access it
+    /// from the device.
+    ///
+    /// On devices without concurrent managed access, the host may not touch unified memory while
+    /// any kernel is running, because the driver can't tell which allocations the kernel uses.
+    /// Once a buffer is attached to a single stream, the driver only needs to wait for work on
+    /// that stream, so the host can access the buffer while kernels on other streams are running.
+    /// This lets kernels on different streams overlap with host work.
+    ///
+    /// The attachment is queued on `stream` and takes effect once earlier work on it has
+    /// completed. After that, kernels on other streams must not access this buffer; doing so is
+    /// undefined behavior. Use [`attach_global`](#method.attach_global) to undo the attachment.
+    ///
+    /// # Errors:
+    ///
+    /// If queueing the attachment fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::memory::*;
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let buffer = UnifiedBuffer::new(&0u32, 1024)?;
+    /// buffer.attach_to_stream(&stream)?;
+    /// // ... launch kernels using `buffer` on `stream` ...
+    /// stream.synchronize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn attach_to_stream(&self, stream: &Stream) -> CudaResult<()> {
+        self.attach(stream, cuda::CUmemAttach_flags::CU_MEM_ATTACH_SINGLE)
+    }
+
+    /// Make this buffer accessible to work on any stream again, undoing
+    /// [`attach_to_stream`](#method.attach_to_stream).
+    ///
+    /// The change is queued on `stream` and takes effect once earlier work on it has completed.
+    ///
+    /// # Errors:
+    ///
+    /// If queueing the attachment fails, returns the error from CUDA.
+    pub fn attach_global(&self, stream: &Stream) -> CudaResult<()> {
+        self.attach(stream, cuda::CUmemAttach_flags::CU_MEM_ATTACH_GLOBAL)
+    }
+
+    fn attach(&self, stream: &Stream, flags: cuda::CUmemAttach_flags) -> CudaResult<()> {
+        if self.capacity * size_of::<T>() > 0 {
+            unsafe {
+                // A length of zero attaches the whole allocation, which the driver requires for
+                // memory from `cuMemAllocManaged`.
+                cuda::cuStreamAttachMemAsync(
+                    stream.as_inner(),
+                    self.buf.as_raw() as u64,
+                    0,
+                    flags as u32,
+                )
+                .to_result()?;
+            }
+        }
+        stream.sync_if_forced()
+    }
+
+    fn prefetch(&self, device: Device, destination: CUdevice, stream: &Stream) -> CudaResult<()> {
+        if device.get_attribute(DeviceAttribute::ConcurrentManagedAccess)? == 0 {
+            return Err(CudaError::NotSupported);
+        }
+        let bytes = self.capacity * size_of::<T>();
+        if bytes > 0 {
+            unsafe {
+                cuda::cuMemPrefetchAsync(
+                    self.buf.as_raw() as u64,
+                    bytes,
+                    destination,
+                    stream.as_inner(),
+                )
+                .to_result()?;
+            }
+        }
+        stream.sync_if_forced()
+    }
+
     /// Creates a `UnifiedBuffer<T>` directly from the raw components of another unified buffer.
     ///
     /// # Safety
@@ -506,7 +801,80 @@ impl<T: DeviceCopy> UnifiedBuffer<T> {
     /// let buffer = unsafe { UnifiedBuffer::from_raw_parts(ptr, size) };
     /// ```
     pub unsafe fn from_raw_parts(ptr: UnifiedPointer<T>, capacity: usize) -> UnifiedBuffer<T> {
-        UnifiedBuffer { buf: ptr, capacity }
+        UnifiedBuffer {
+            buf: ptr,
+            capacity,
+            panic_on_drop_error: true,
+        }
+    }
+
+    /// Choose whether dropping this buffer panics if freeing the memory fails.
+    ///
+    /// This is true by default. When false, the error is passed to the handler set with
+    /// [`set_drop_error_handler`](fn.set_drop_error_handler.html) instead, which is useful for
+    /// shutdown paths that must not abort. This does not affect
+    /// [`UnifiedBuffer::drop`](#method.drop), which always returns the error.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut buffer = UnifiedBuffer::new(&0u64, 5).unwrap();
+    /// buffer.set_panic_on_drop_error(false);
+    /// ```
+    pub fn set_panic_on_drop_error(&mut self, panic: bool) {
+        self.panic_on_drop_error = panic;
+    }
+
+    /// Reinterpret this buffer as a buffer of `U`'s, without copying.
+    ///
+    /// The length of the new buffer is the size of this buffer in bytes divided by the size of
+    /// `U`. Since unified memory is accessible from the host, the new buffer's contents are simply
+    /// the bytes of the old buffer viewed as `U`'s.
+    ///
+    /// # Errors:
+    ///
+    /// Returns `InvalidValue` and the unchanged buffer if the buffer is not aligned for `U`, if
+    /// its size in bytes is not a multiple of the size of `U`, or if `U` is zero-sized and the
+    /// buffer is not empty.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let bytes = UnifiedBuffer::new(&0xFFu8, 16).unwrap();
+    /// let words: UnifiedBuffer<u32> = bytes.cast().map_err(|(_, e)| e).unwrap();
+    /// assert_eq!(&[0xFFFF_FFFFu32; 4], words.as_slice());
+    /// ```
+    pub fn cast<U: DeviceCopy>(self) -> Result<UnifiedBuffer<U>, (Self, CudaError)> {
+        let bytes = self.capacity * size_of::<T>();
+        let size_u = size_of::<U>();
+        let capacity = if bytes == 0 {
+            0
+        } else if size_u == 0
+            || !bytes.is_multiple_of(size_u)
+            || !(self.buf.as_raw() as usize).is_multiple_of(align_of::<U>())
+        {
+            return Err((self, CudaError::InvalidValue));
+        } else {
+            bytes / size_u
+        };
+
+        let buf = if bytes == 0 {
+            unsafe { UnifiedPointer::wrap(ptr::NonNull::dangling().as_ptr()) }
+        } else {
+            self.buf.cast::<U>()
+        };
+        let panic_on_drop_error = self.panic_on_drop_error;
+        // The allocation (if any) now belongs to the new buffer.
+        mem::forget(self);
+        Ok(UnifiedBuffer {
+            buf,
+            capacity,
+            panic_on_drop_error,
+        })
     }
 
     /// Destroy a `UnifiedBuffer`, returning an error.
@@ -542,7 +910,11 @@ impl<T: DeviceCopy> UnifiedBuffer<T> {
                         mem::forget(uni_buf);
                         Ok(())
                     }
-                    Err(e) => Err((e, UnifiedBuffer::from_raw_parts(ptr, capacity))),
+                    Err(e) => {
+                        let mut buf = UnifiedBuffer::from_raw_parts(ptr, capacity);
+                        buf.panic_on_drop_error = uni_buf.panic_on_drop_error;
+                        Err((e, buf))
+                    }
                 }
             }
         } else {
@@ -586,6 +958,272 @@ impl<T: DeviceCopy> Drop for UnifiedBuffer<T> {
         }
 
         if self.capacity > 0 && mem::size_of::<T>() > 0 {
+            // Panic if this fails, unless the caller has asked for errors to be handled instead.
+            unsafe {
+                let ptr = mem::replace(&mut self.buf, UnifiedPointer::null());
+                let result = cuda_free_unified(ptr);
+                if self.panic_on_drop_error {
+                    result.expect("Failed to deallocate CUDA unified memory.");
+                } else if let Err(e) = result {
+                    handle_drop_error(e);
+                }
+            }
+        }
+        self.capacity = 0;
+    }
+}
+
+/// Growable array in unified memory, similar to `Vec`.
+///
+/// Unlike [`UnifiedBuffer`](struct.UnifiedBuffer.html), a `UnifiedVec` can grow as elements are
+/// pushed onto it. When the capacity is exceeded, a new allocation of twice the size is made, the
+/// existing elements are copied into it and the old allocation is freed. As with `Vec`, this
+/// invalidates any pointers into the old allocation.
+///
+/// See the [`module-level documentation`](../memory/index.html) for more details on unified memory.
+#[derive(Debug)]
+pub struct UnifiedVec<T: DeviceCopy> {
+    buf: UnifiedPointer<T>,
+    capacity: usize,
+    len: usize,
+}
+impl<T: DeviceCopy> UnifiedVec<T> {
+    /// Create a new, empty `UnifiedVec`.
+    ///
+    /// This does not allocate any memory until elements are pushed onto it.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut vec = UnifiedVec::new();
+    /// vec.push(5u64).unwrap();
+    /// ```
+    pub fn new() -> Self {
+        UnifiedVec {
+            buf: unsafe { UnifiedPointer::wrap(ptr::NonNull::dangling().as_ptr()) },
+            capacity: if size_of::<T>() == 0 { usize::MAX } else { 0 },
+            len: 0,
+        }
+    }
+
+    /// Create a new, empty `UnifiedVec` with space for at least `capacity` elements.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA. If `capacity` is large enough that
+    /// `capacity * mem::sizeof::<T>()` overflows usize, then returns InvalidMemoryAllocation.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let vec = UnifiedVec::<u64>::with_capacity(10).unwrap();
+    /// assert!(vec.capacity() >= 10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> CudaResult<Self> {
+        let mut vec = UnifiedVec::new();
+        vec.reserve(capacity)?;
+        Ok(vec)
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Reserve capacity for at least `additional` more elements.
+    ///
+    /// # Errors:
+    ///
+    /// If the allocation fails, returns the error from CUDA. If the new capacity would overflow
+    /// usize, returns InvalidMemoryAllocation.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut vec = UnifiedVec::<u64>::new();
+    /// vec.reserve(10).unwrap();
+    /// assert!(vec.capacity() >= 10);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) -> CudaResult<()> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(CudaError::InvalidMemoryAllocation)?;
+        if required <= self.capacity {
+            return Ok(());
+        }
+
+        let new_capacity = cmp::max(required, self.capacity.saturating_mul(2));
+        unsafe {
+            let mut new_buf = cuda_malloc_unified::<T>(new_capacity)?;
+            ptr::copy_nonoverlapping(self.buf.as_raw(), new_buf.as_raw_mut(), self.len);
+
+            let old_buf = mem::replace(&mut self.buf, new_buf);
+            let old_capacity = mem::replace(&mut self.capacity, new_capacity);
+            if old_capacity > 0 {
+                cuda_free_unified(old_buf)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Append an element to the end of the vector, growing the allocation if necessary.
+    ///
+    /// # Errors:
+    ///
+    /// If a reallocation is needed and fails, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut vec = UnifiedVec::new();
+    /// vec.push(1u64).unwrap();
+    /// vec.push(2u64).unwrap();
+    /// assert_eq!(&[1u64, 2], vec.as_slice());
+    /// ```
+    pub fn push(&mut self, value: T) -> CudaResult<()> {
+        if self.len == self.capacity {
+            self.reserve(1)?;
+        }
+        unsafe {
+            ptr::write(self.buf.as_raw_mut().add(self.len), value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Extracts a slice containing the entire vector.
+    ///
+    /// Equivalent to `&s[..]`.
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// Extracts a mutable slice of the entire vector.
+    ///
+    /// Equivalent to `&mut s[..]`.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    /// Returns a `UnifiedPointer<T>` to the vector's buffer.
+    ///
+    /// The caller must ensure that the vector outlives the returned pointer, or it will end up
+    /// pointing to garbage. Pushing onto the vector may reallocate it, which also invalidates the
+    /// pointer.
+    pub fn as_unified_ptr(&mut self) -> UnifiedPointer<T> {
+        self.buf
+    }
+
+    /// Destroy a `UnifiedVec`, returning an error.
+    ///
+    /// Deallocating unified memory can return errors from previous asynchronous work. This function
+    /// destroys the given vector and returns the error and the un-destroyed vector on failure.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # let _context = rustacuda::quick_init().unwrap();
+    /// use rustacuda::memory::*;
+    /// let mut x = UnifiedVec::new();
+    /// x.push(10u32).unwrap();
+    /// match UnifiedVec::drop(x) {
+    ///     Ok(()) => println!("Successfully destroyed"),
+    ///     Err((e, vec)) => {
+    ///         println!("Failed to destroy vector: {:?}", e);
+    ///         // Do something with vec
+    ///     },
+    /// }
+    /// ```
+    pub fn drop(mut vec: UnifiedVec<T>) -> DropResult<UnifiedVec<T>> {
+        if vec.buf.is_null() {
+            return Ok(());
+        }
+
+        if vec.capacity > 0 && size_of::<T>() > 0 {
+            let capacity = vec.capacity;
+            let len = vec.len;
+            let ptr = mem::replace(&mut vec.buf, UnifiedPointer::null());
+            unsafe {
+                match cuda_free_unified(ptr) {
+                    Ok(()) => {
+                        mem::forget(vec);
+                        Ok(())
+                    }
+                    Err(e) => Err((
+                        e,
+                        UnifiedVec {
+                            buf: ptr,
+                            capacity,
+                            len,
+                        },
+                    )),
+                }
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+impl<T: DeviceCopy> Default for UnifiedVec<T> {
+    fn default() -> Self {
+        UnifiedVec::new()
+    }
+}
+impl<T: DeviceCopy> Extend<T> for UnifiedVec<T> {
+    /// Extend the vector with the contents of an iterator.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if growing the vector fails. Use `push` to handle allocation errors.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower)
+            .expect("Failed to allocate CUDA unified memory.");
+        for value in iter {
+            self.push(value)
+                .expect("Failed to allocate CUDA unified memory.");
+        }
+    }
+}
+impl<T: DeviceCopy> AsRef<[T]> for UnifiedVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+impl<T: DeviceCopy> AsMut<[T]> for UnifiedVec<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+impl<T: DeviceCopy> Deref for UnifiedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.buf.as_raw(), self.len) }
+    }
+}
+impl<T: DeviceCopy> DerefMut for UnifiedVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.buf.as_raw_mut(), self.len) }
+    }
+}
+impl<T: DeviceCopy> Drop for UnifiedVec<T> {
+    fn drop(&mut self) {
+        if self.buf.is_null() {
+            return;
+        }
+
+        if self.capacity > 0 && size_of::<T>() > 0 {
             // No choice but to panic if this fails.
             unsafe {
                 let ptr = mem::replace(&mut self.buf, UnifiedPointer::null());
@@ -593,6 +1231,7 @@ impl<T: DeviceCopy> Drop for UnifiedBuffer<T> {
             }
         }
         self.capacity = 0;
+        self.len = 0;
     }
 }
 
@@ -719,4 +1358,249 @@ mod test_unified_buffer {
         let err = UnifiedBuffer::new(&0u64, ::std::usize::MAX - 1).unwrap_err();
         assert_eq!(CudaError::InvalidMemoryAllocation, err);
     }
+
+    #[test]
+    fn test_prefetch_to_host_after() {
+        use crate::event::EventFlags;
+        use crate::stream::StreamFlags;
+
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let event = Event::new(EventFlags::DISABLE_TIMING).unwrap();
+        let mut buffer = UnifiedBuffer::new(&0u64, 1024).unwrap();
+        for (i, x) in buffer.iter_mut().enumerate() {
+            *x = i as u64;
+        }
+
+        event.record(&stream).unwrap();
+        buffer.prefetch_to_host_after(&stream, &event).unwrap();
+        stream.synchronize().unwrap();
+
+        for (i, x) in buffer.iter().enumerate() {
+            assert_eq!(i as u64, *x);
+        }
+    }
+
+    #[test]
+    fn test_cast() {
+        let _context = crate::quick_init().unwrap();
+        let mut bytes = UnifiedBuffer::new(&0u8, 16).unwrap();
+        for (i, x) in bytes.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        let words = bytes.cast::<u32>().map_err(|(_, e)| e).unwrap();
+        assert_eq!(4, words.len());
+        for (i, word) in words.iter().enumerate() {
+            let expected = [
+                4 * i as u8,
+                4 * i as u8 + 1,
+                4 * i as u8 + 2,
+                4 * i as u8 + 3,
+            ];
+            assert_eq!(u32::from_ne_bytes(expected), *word);
+        }
+    }
+
+    #[test]
+    fn test_cast_size_mismatch() {
+        let _context = crate::quick_init().unwrap();
+        let bytes = UnifiedBuffer::new(&0u8, 15).unwrap();
+        let (bytes, err) = bytes.cast::<u32>().unwrap_err();
+        assert_eq!(CudaError::InvalidValue, err);
+        assert_eq!(15, bytes.len());
+    }
+
+    #[test]
+    fn test_cast_misaligned() {
+        let _context = crate::quick_init().unwrap();
+        let mut bytes = UnifiedBuffer::new(&0u8, 17).unwrap();
+        let offset =
+            unsafe { UnifiedBuffer::from_raw_parts(bytes.as_unified_ptr().wrapping_add(1), 16) };
+        let (offset, err) = offset.cast::<u32>().unwrap_err();
+        assert_eq!(CudaError::InvalidValue, err);
+        // `offset` doesn't own its memory, so it must not be freed.
+        mem::forget(offset);
+    }
+
+    #[test]
+    fn test_prefetch_then_launch() {
+        use crate::function::TypedFunction;
+        use crate::module::Module;
+        use crate::stream::StreamFlags;
+        use std::ffi::CString;
+
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let device = CurrentContext::get_device().unwrap();
+        let mut x = UnifiedBuffer::new(&2.0f32, 128).unwrap();
+        let mut y = UnifiedBuffer::new(&1.0f32, 128).unwrap();
+        let mut out = UnifiedBuffer::new(&0.0f32, 128).unwrap();
+
+        if device
+            .get_attribute(DeviceAttribute::ConcurrentManagedAccess)
+            .unwrap()
+            == 0
+        {
+            assert_eq!(
+                Err(CudaError::NotSupported),
+                x.prefetch_to_device(device, &stream)
+            );
+            return;
+        }
+
+        for buffer in &[&x, &y, &out] {
+            buffer.prefetch_to_device(device, &stream).unwrap();
+        }
+        let ptx = CString::new(include_str!("../../resources/add.ptx")).unwrap();
+        let module = Module::load_from_string(&ptx).unwrap();
+        let function = module.get_function(&CString::new("sum").unwrap()).unwrap();
+        let sum = TypedFunction::new(function);
+        unsafe {
+            let args = (
+                x.as_unified_ptr(),
+                y.as_unified_ptr(),
+                out.as_unified_ptr(),
+                out.len() as i32,
+            );
+            sum.launch(&stream, 1, 128, 0, args).unwrap();
+        }
+        out.prefetch_to_host(&stream).unwrap();
+        stream.synchronize().unwrap();
+        for value in out.iter() {
+            assert_eq!(3.0, *value);
+        }
+    }
+
+    #[test]
+    fn test_attach_to_stream_around_launch() {
+        use crate::function::TypedFunction;
+        use crate::module::Module;
+        use crate::stream::StreamFlags;
+        use std::ffi::CString;
+
+        let _context = crate::quick_init().unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let mut x = UnifiedBuffer::new(&2.0f32, 128).unwrap();
+        let mut y = UnifiedBuffer::new(&1.0f32, 128).unwrap();
+        let mut out = UnifiedBuffer::new(&0.0f32, 128).unwrap();
+        for buffer in &[&x, &y, &out] {
+            buffer.attach_to_stream(&stream).unwrap();
+        }
+
+        let ptx = CString::new(include_str!("../../resources/add.ptx")).unwrap();
+        let module = Module::load_from_string(&ptx).unwrap();
+        let function = module.get_function(&CString::new("sum").unwrap()).unwrap();
+        let sum = TypedFunction::new(function);
+        unsafe {
+            let args = (
+                x.as_unified_ptr(),
+                y.as_unified_ptr(),
+                out.as_unified_ptr(),
+                out.len() as i32,
+            );
+            sum.launch(&stream, 1, 128, 0, args).unwrap();
+        }
+        stream.synchronize().unwrap();
+        for value in out.iter() {
+            assert_eq!(3.0, *value);
+        }
+
+        for buffer in &[&x, &y, &out] {
+            buffer.attach_global(&stream).unwrap();
+        }
+        stream.synchronize().unwrap();
+    }
+
+    #[test]
+    fn test_advise() {
+        let _context = crate::quick_init().unwrap();
+        let device = CurrentContext::get_device().unwrap();
+        if device
+            .get_attribute(DeviceAttribute::ConcurrentManagedAccess)
+            .unwrap()
+            == 0
+        {
+            return;
+        }
+
+        let buffer = UnifiedBuffer::new(&0u64, 1024).unwrap();
+        let uni_box = UnifiedBox::new(0u64).unwrap();
+        for &advice in &[
+            MemAdvise::SetReadMostly,
+            MemAdvise::UnsetReadMostly,
+            MemAdvise::SetPreferredLocation,
+            MemAdvise::UnsetPreferredLocation,
+            MemAdvise::SetAccessedBy,
+            MemAdvise::UnsetAccessedBy,
+        ] {
+            buffer.advise(advice, device).unwrap();
+            uni_box.advise(advice, device).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_unified_vec {
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct ZeroSizedType;
+    unsafe impl DeviceCopy for ZeroSizedType {}
+
+    #[test]
+    fn test_push() {
+        let _context = crate::quick_init().unwrap();
+        let mut vec = UnifiedVec::new();
+        assert_eq!(0, vec.capacity());
+        for i in 0..1000u64 {
+            vec.push(i).unwrap();
+        }
+        assert_eq!(1000, vec.len());
+        assert!(vec.capacity() >= 1000);
+        for (i, x) in vec.iter().enumerate() {
+            assert_eq!(i as u64, *x);
+        }
+    }
+
+    #[test]
+    fn test_extend() {
+        let _context = crate::quick_init().unwrap();
+        let mut vec = UnifiedVec::new();
+        vec.extend(0..500u32);
+        vec.extend(500..1000u32);
+        assert_eq!(1000, vec.len());
+        for (i, x) in vec.iter().enumerate() {
+            assert_eq!(i as u32, *x);
+        }
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let _context = crate::quick_init().unwrap();
+        let mut vec = UnifiedVec::with_capacity(10).unwrap();
+        let ptr = vec.as_unified_ptr();
+        for i in 0..10u64 {
+            vec.push(i).unwrap();
+        }
+        assert_eq!(ptr, vec.as_unified_ptr());
+        UnifiedVec::drop(vec).unwrap();
+    }
+
+    #[test]
+    fn zero_size_type() {
+        let _context = crate::quick_init().unwrap();
+        let mut vec = UnifiedVec::new();
+        for _ in 0..10 {
+            vec.push(ZeroSizedType).unwrap();
+        }
+        assert_eq!(10, vec.len());
+        drop(vec);
+    }
+
+    #[test]
+    fn overflows_usize() {
+        let _context = crate::quick_init().unwrap();
+        let err = UnifiedVec::<u64>::with_capacity(usize::MAX - 1).unwrap_err();
+        assert_eq!(CudaError::InvalidMemoryAllocation, err);
+    }
 }
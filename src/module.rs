@@ -1,15 +1,55 @@
 //! Functions and types for working with CUDA modules.
 
-use crate::error::{CudaResult, DropResult, ToResult};
+use crate::error::{CudaError, CudaResult, DropResult, ToResult};
 use crate::function::Function;
 use crate::memory::{CopyDestination, DeviceCopy, DevicePointer};
 use cuda_sys::cuda;
+use std::borrow::Cow;
 use std::ffi::{c_void, CStr};
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
 use std::ptr;
 
+/// Options for JIT-compiling a module with
+/// [`Module::load_data_ex`](struct.Module.html#method.load_data_ex).
+///
+/// Options left at their default values are not passed to the JIT compiler, so the driver's
+/// defaults apply.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct JitOptions {
+    /// Maximum number of registers each thread may use.
+    pub max_registers: Option<u32>,
+    /// Optimization level, from 0 (none) to 4 (the driver's default).
+    pub optimization_level: Option<u32>,
+    /// Compile for the device of the current context. The driver already does this unless a
+    /// different target is specified, so this is only needed to make the choice explicit.
+    pub target_from_context: bool,
+    /// Generate line-number information, for profilers and debuggers.
+    pub generate_line_info: bool,
+}
+
+// Size in bytes of each of the JIT info and error log buffers.
+const JIT_LOG_SIZE: usize = 8192;
+
+// Returns `image` if it is already null-terminated, or a null-terminated copy of it otherwise.
+fn null_terminated(image: &[u8]) -> Cow<'_, [u8]> {
+    if image.last() == Some(&0) {
+        Cow::Borrowed(image)
+    } else {
+        let mut terminated = Vec::with_capacity(image.len() + 1);
+        terminated.extend_from_slice(image);
+        terminated.push(0);
+        Cow::Owned(terminated)
+    }
+}
+
+// Converts a null-terminated JIT log buffer to a string, dropping the terminator and padding.
+fn log_to_string(log: &[u8]) -> String {
+    let len = log.iter().position(|&b| b == 0).unwrap_or(log.len());
+    String::from_utf8_lossy(&log[..len]).into_owned()
+}
+
 /// A compiled CUDA module, loaded into a context.
 #[derive(Debug)]
 pub struct Module {
@@ -71,24 +111,144 @@ impl Module {
     /// # }
     /// ```
     pub fn load_from_string(image: &CStr) -> CudaResult<Module> {
+        unsafe { Module::load_data(image.as_ptr() as *const u8) }
+    }
+
+    /// Load a module from a byte slice.
+    ///
+    /// This is like [`load_from_string`](#method.load_from_string), but accepts the image as
+    /// bytes, such as those returned by `include_bytes!`. If `image` is not already
+    /// null-terminated, it is copied into a temporary null-terminated buffer before loading.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::module::Module;
+    ///
+    /// let module = Module::load_from_bytes(include_bytes!("../resources/add.ptx"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load_from_bytes(image: &[u8]) -> CudaResult<Module> {
+        let image = null_terminated(image);
+        unsafe { Module::load_data(image.as_ptr()) }
+    }
+
+    /// Load a module from a byte slice, JIT-compiling it with the given options.
+    ///
+    /// This is mostly useful for PTX images, which are compiled for the current device when they
+    /// are loaded. As with [`load_from_bytes`](#method.load_from_bytes), `image` is copied into a
+    /// null-terminated buffer if necessary.
+    ///
+    /// On success, returns the module along with the JIT compiler's information log. On failure,
+    /// returns the error along with the JIT compiler's error log, which usually explains why the
+    /// compilation failed. Either log may be empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::module::{JitOptions, Module};
+    ///
+    /// let options = JitOptions {
+    ///     optimization_level: Some(4),
+    ///     generate_line_info: true,
+    ///     ..JitOptions::default()
+    /// };
+    /// let ptx = include_bytes!("../resources/add.ptx");
+    /// match Module::load_data_ex(ptx, &options) {
+    ///     Ok((module, info_log)) => println!("JIT info: {}", info_log),
+    ///     Err((e, error_log)) => println!("JIT failed with {:?}: {}", e, error_log),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load_data_ex(
+        image: &[u8],
+        options: &JitOptions,
+    ) -> Result<(Module, String), (CudaError, String)> {
+        let image = null_terminated(image);
+        let mut info_log = vec![0u8; JIT_LOG_SIZE];
+        let mut error_log = vec![0u8; JIT_LOG_SIZE];
+
+        // Option values are passed as pointer-sized values, so integers are cast to pointers.
+        let mut keys = vec![
+            cuda::CUjit_option::CU_JIT_INFO_LOG_BUFFER,
+            cuda::CUjit_option::CU_JIT_INFO_LOG_BUFFER_SIZE_BYTES,
+            cuda::CUjit_option::CU_JIT_ERROR_LOG_BUFFER,
+            cuda::CUjit_option::CU_JIT_ERROR_LOG_BUFFER_SIZE_BYTES,
+        ];
+        let mut values = vec![
+            info_log.as_mut_ptr() as *mut c_void,
+            JIT_LOG_SIZE as *mut c_void,
+            error_log.as_mut_ptr() as *mut c_void,
+            JIT_LOG_SIZE as *mut c_void,
+        ];
+        if let Some(max_registers) = options.max_registers {
+            keys.push(cuda::CUjit_option::CU_JIT_MAX_REGISTERS);
+            values.push(max_registers as usize as *mut c_void);
+        }
+        if let Some(level) = options.optimization_level {
+            keys.push(cuda::CUjit_option::CU_JIT_OPTIMIZATION_LEVEL);
+            values.push(level as usize as *mut c_void);
+        }
+        if options.target_from_context {
+            keys.push(cuda::CUjit_option::CU_JIT_TARGET_FROM_CUCONTEXT);
+            values.push(ptr::null_mut());
+        }
+        if options.generate_line_info {
+            keys.push(cuda::CUjit_option::CU_JIT_GENERATE_LINE_INFO);
+            values.push(true as usize as *mut c_void);
+        }
+
         unsafe {
             let mut module = Module {
                 inner: ptr::null_mut(),
             };
-            cuda::cuModuleLoadData(
+            let result = cuda::cuModuleLoadDataEx(
                 &mut module.inner as *mut cuda::CUmodule,
                 image.as_ptr() as *const c_void,
+                keys.len() as u32,
+                keys.as_mut_ptr(),
+                values.as_mut_ptr(),
             )
-            .to_result()?;
-            Ok(module)
+            .to_result();
+            match result {
+                Ok(()) => Ok((module, log_to_string(&info_log))),
+                Err(e) => Err((e, log_to_string(&error_log))),
+            }
         }
     }
 
+    // Loads a module from a pointer to a null-terminated (or self-describing binary) image.
+    unsafe fn load_data(image: *const u8) -> CudaResult<Module> {
+        let mut module = Module {
+            inner: ptr::null_mut(),
+        };
+        cuda::cuModuleLoadData(
+            &mut module.inner as *mut cuda::CUmodule,
+            image as *const c_void,
+        )
+        .to_result()?;
+        Ok(module)
+    }
+
     /// Get a reference to a global symbol, which can then be copied to/from.
     ///
-    /// # Panics:
+    /// This works for both `__device__` and `__constant__` variables. Kernels launched after a
+    /// value is copied into the symbol see the new value.
     ///
-    /// This function panics if the size of the symbol is not the same as the `mem::sizeof<T>()`.
+    /// # Errors:
+    ///
+    /// If the size of the symbol is not the same as `mem::size_of::<T>()`, returns InvalidValue.
+    /// If the symbol does not exist, returns the error from CUDA.
     ///
     /// # Examples:
     ///
@@ -123,7 +283,9 @@ impl Module {
                 name.as_ptr(),
             )
             .to_result()?;
-            assert_eq!(size, mem::size_of::<T>());
+            if size != size_of::<T>() {
+                return Err(CudaError::InvalidValue);
+            }
             Ok(Symbol {
                 ptr,
                 module: PhantomData,
@@ -293,6 +455,41 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_load_from_bytes() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let module = Module::load_from_bytes(include_bytes!("../resources/add.ptx"))?;
+        drop(module);
+
+        let terminated = CString::new(include_str!("../resources/add.ptx"))?;
+        let module = Module::load_from_bytes(terminated.as_bytes_with_nul())?;
+        drop(module);
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_data_ex() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let options = JitOptions {
+            max_registers: Some(32),
+            optimization_level: Some(3),
+            target_from_context: true,
+            generate_line_info: true,
+        };
+        let (module, _info_log) =
+            Module::load_data_ex(include_bytes!("../resources/add.ptx"), &options)
+                .map_err(|(e, _)| e)?;
+        drop(module);
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_data_ex_reports_errors() {
+        let _context = quick_init();
+        let result = Module::load_data_ex(b"this is not ptx", &JitOptions::default());
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_copy_from_module() -> Result<(), Box<dyn Error>> {
         let _context = quick_init();
@@ -309,6 +506,44 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_get_global_wrong_size() -> Result<(), Box<dyn Error>> {
+        let _context = quick_init();
+        let module = Module::load_from_bytes(include_bytes!("../resources/add.ptx"))?;
+        let constant_name = CString::new("my_constant")?;
+        let result = module.get_global::<u64>(&constant_name);
+        assert_eq!(CudaError::InvalidValue, result.unwrap_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_kernel_reads_uploaded_constant() -> Result<(), Box<dyn Error>> {
+        use crate::function::TypedFunction;
+        use crate::memory::DeviceBox;
+        use crate::stream::{Stream, StreamFlags};
+
+        let _context = quick_init();
+        let module = Module::load_from_bytes(include_bytes!("../resources/add.ptx"))?;
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+
+        let constant_name = CString::new("my_constant")?;
+        let mut symbol = module.get_global::<u32>(&constant_name)?;
+        symbol.copy_from(&27)?;
+
+        let read_constant =
+            TypedFunction::new(module.get_function(&CString::new("read_constant")?)?);
+        let mut out = DeviceBox::new(&0u32)?;
+        unsafe {
+            read_constant.launch(&stream, 1, 1, 0, (out.as_device_ptr(),))?;
+        }
+        stream.synchronize()?;
+
+        let mut seen = 0u32;
+        out.copy_to(&mut seen)?;
+        assert_eq!(27, seen);
+        Ok(())
+    }
+
     #[test]
     fn test_copy_to_module() -> Result<(), Box<dyn Error>> {
         let _context = quick_init();
@@ -0,0 +1,255 @@
+//! Composable stages of device work which run in order on a single stream.
+//!
+//! A [`Stage`](trait.Stage.html) takes a slice of device memory, queues some work on a stream,
+//! such as a kernel launch, and returns a new device buffer holding its output. Stages can be
+//! chained with [`Stage::then`](trait.Stage.html#method.then) into a
+//! [`Pipeline`](struct.Pipeline.html), which feeds the output of one stage into the next on the
+//! same stream. Since work on a stream runs in order, the intermediate results never need to be
+//! copied back to the host or synchronized between stages.
+
+use crate::error::CudaResult;
+use crate::memory::{DeviceBuffer, DeviceCopy, DeviceSlice};
+use crate::stream::Stream;
+use std::future::Future;
+
+/// One step of device work in a [`Pipeline`](struct.Pipeline.html).
+///
+/// Implementations are usually written with an `async fn`:
+///
+/// ```
+/// # use rustacuda::*;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let _ctx = quick_init()?;
+/// use rustacuda::error::CudaResult;
+/// use rustacuda::memory::*;
+/// use rustacuda::pipeline::Stage;
+/// use rustacuda::stream::Stream;
+///
+/// // Copies its input, standing in for a kernel launch.
+/// struct Identity;
+///
+/// impl Stage for Identity {
+///     type Input = f32;
+///     type Output = f32;
+///
+///     async fn run(
+///         &self,
+///         input: &DeviceSlice<f32>,
+///         stream: &Stream,
+///     ) -> CudaResult<DeviceBuffer<f32>> {
+///         let mut output = unsafe { DeviceBuffer::uninitialized(input.len())? };
+///         unsafe { output.async_copy_from(input, stream)? };
+///         Ok(output)
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait Stage {
+    /// The element type of the stage's input.
+    type Input: DeviceCopy;
+    /// The element type of the stage's output.
+    type Output: DeviceCopy;
+
+    /// Queue this stage's work on `stream`, reading from `input`, and return the buffer it writes
+    /// its output to.
+    ///
+    /// The returned future may resolve as soon as the work has been queued, before it has
+    /// completed. The output buffer can be used by further work on the same stream straight
+    /// away, but must not be read from the host or from other streams until the stream's work
+    /// has completed, for example by awaiting
+    /// [`Stream::completion`](../stream/struct.Stream.html#method.completion).
+    ///
+    /// # Errors:
+    ///
+    /// If allocating the output or queueing the work fails, returns the error from CUDA.
+    fn run<'a>(
+        &'a self,
+        input: &'a DeviceSlice<Self::Input>,
+        stream: &'a Stream,
+    ) -> impl Future<Output = CudaResult<DeviceBuffer<Self::Output>>> + 'a;
+
+    /// Chain `next` after this stage, so that the output of this stage is the input of `next`.
+    fn then<S>(self, next: S) -> Pipeline<Self, S>
+    where
+        Self: Sized,
+        S: Stage<Input = Self::Output>,
+    {
+        Pipeline {
+            first: self,
+            second: next,
+        }
+    }
+}
+
+/// Two stages run one after the other on the same stream, created by
+/// [`Stage::then`](trait.Stage.html#method.then).
+///
+/// A `Pipeline` is itself a `Stage`, so longer pipelines can be built by chaining further stages.
+///
+/// Unlike most stages, a `Pipeline`'s future only resolves once all of the stream's work has
+/// completed, because the intermediate buffer between the two stages has to stay allocated until
+/// the second stage has finished reading it. The final output can therefore be read immediately.
+///
+/// # Examples:
+///
+/// ```
+/// # use rustacuda::*;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let _ctx = quick_init()?;
+/// # use rustacuda::error::CudaResult;
+/// # use rustacuda::memory::*;
+/// # use rustacuda::stream::{Stream, StreamFlags};
+/// use rustacuda::pipeline::Stage;
+/// # struct Identity;
+/// # impl Stage for Identity {
+/// #     type Input = f32;
+/// #     type Output = f32;
+/// #     async fn run(
+/// #         &self,
+/// #         input: &DeviceSlice<f32>,
+/// #         stream: &Stream,
+/// #     ) -> CudaResult<DeviceBuffer<f32>> {
+/// #         let mut output = unsafe { DeviceBuffer::uninitialized(input.len())? };
+/// #         unsafe { output.async_copy_from(input, stream)? };
+/// #         Ok(output)
+/// #     }
+/// # }
+///
+/// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+/// let input = DeviceBuffer::from_slice(&[1.0f32, 2.0, 3.0])?;
+/// let pipeline = Identity.then(Identity);
+/// // Spawn or block on this with your executor of choice.
+/// let output = pipeline.run(&input, &stream);
+/// # drop(output);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Pipeline<A, B> {
+    first: A,
+    second: B,
+}
+impl<A, B> Pipeline<A, B> {
+    /// Split the pipeline back into its two stages.
+    pub fn into_stages(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+impl<A, B> Stage for Pipeline<A, B>
+where
+    A: Stage,
+    B: Stage<Input = A::Output>,
+{
+    type Input = A::Input;
+    type Output = B::Output;
+
+    async fn run(
+        &self,
+        input: &DeviceSlice<A::Input>,
+        stream: &Stream,
+    ) -> CudaResult<DeviceBuffer<B::Output>> {
+        let intermediate = self.first.run(input, stream).await?;
+        let output = self.second.run(&intermediate, stream).await?;
+        stream.completion()?.await?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::function::TypedFunction;
+    use crate::memory::DevicePointer;
+    use crate::module::Module;
+    use crate::stream::StreamFlags;
+    use crate::test_util::block_on;
+    use std::ffi::CString;
+
+    type SumArgs = (
+        DevicePointer<f32>,
+        DevicePointer<f32>,
+        DevicePointer<f32>,
+        i32,
+    );
+
+    // Launches `sum(x, y, out, len)` over `x` and the given `y`, or over `x` twice if `y` is None.
+    unsafe fn launch_sum(
+        sum: &TypedFunction<SumArgs>,
+        x: &DeviceSlice<f32>,
+        y: Option<&DeviceBuffer<f32>>,
+        stream: &Stream,
+    ) -> CudaResult<DeviceBuffer<f32>> {
+        let mut out = DeviceBuffer::uninitialized(x.len())?;
+        let x_ptr = DevicePointer::wrap(x.as_ptr() as *mut f32);
+        let y_ptr = y.map_or(x_ptr, |y| DevicePointer::wrap(y.as_ptr() as *mut f32));
+        sum.launch(
+            stream,
+            1,
+            x.len() as u32,
+            0,
+            (x_ptr, y_ptr, out.as_device_ptr(), x.len() as i32),
+        )?;
+        Ok(out)
+    }
+
+    struct Double<'a> {
+        sum: &'a TypedFunction<'a, SumArgs>,
+    }
+    impl<'a> Stage for Double<'a> {
+        type Input = f32;
+        type Output = f32;
+
+        async fn run(
+            &self,
+            input: &DeviceSlice<f32>,
+            stream: &Stream,
+        ) -> CudaResult<DeviceBuffer<f32>> {
+            unsafe { launch_sum(self.sum, input, None, stream) }
+        }
+    }
+
+    struct Increment<'a> {
+        sum: &'a TypedFunction<'a, SumArgs>,
+        ones: DeviceBuffer<f32>,
+    }
+    impl<'a> Stage for Increment<'a> {
+        type Input = f32;
+        type Output = f32;
+
+        async fn run(
+            &self,
+            input: &DeviceSlice<f32>,
+            stream: &Stream,
+        ) -> CudaResult<DeviceBuffer<f32>> {
+            unsafe { launch_sum(self.sum, input, Some(&self.ones), stream) }
+        }
+    }
+
+    #[test]
+    fn test_double_then_increment() {
+        let _context = crate::quick_init().unwrap();
+        let module = Module::load_from_bytes(include_bytes!("../resources/add.ptx")).unwrap();
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        let sum = TypedFunction::new(module.get_function(&CString::new("sum").unwrap()).unwrap());
+
+        let input = DeviceBuffer::from_slice(&[1.0f32, 2.0, 3.0, 4.0]).unwrap();
+        let pipeline = Double { sum: &sum }.then(Increment {
+            sum: &sum,
+            ones: DeviceBuffer::from_slice(&[1.0f32; 4]).unwrap(),
+        });
+        let output = block_on(pipeline.run(&input, &stream)).unwrap();
+        assert_eq!(vec![3.0f32, 5.0, 7.0, 9.0], output.copy_to_vec().unwrap());
+
+        // Pipelines are stages too, so they can be chained further.
+        let (double, increment) = pipeline.into_stages();
+        let pipeline = double.then(increment).then(Double { sum: &sum });
+        let output = block_on(pipeline.run(&input, &stream)).unwrap();
+        assert_eq!(
+            vec![6.0f32, 10.0, 14.0, 18.0],
+            output.copy_to_vec().unwrap()
+        );
+    }
+}
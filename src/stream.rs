@@ -6,17 +6,29 @@
 //! A stream is required for all asynchronous tasks in CUDA, such as kernel launches and
 //! asynchronous memory copying. Each task in a stream is performed in the order it was scheduled,
 //! and tasks within a stream cannot overlap. Tasks scheduled in multiple streams may interleave or
-//! execute concurrently. Sequencing between multiple streams can be achieved using events, which
-//! are not currently supported by RustaCUDA. Finally, the host can wait for all work scheduled in
-//! a stream to be completed.
+//! execute concurrently. Sequencing between multiple streams can be achieved using
+//! [events](../event/index.html). Finally, the host can wait for all work scheduled in a stream to
+//! be completed.
 
-use crate::error::{CudaResult, DropResult, ToResult};
-use crate::function::{BlockSize, Function, GridSize};
+use crate::error::{ContextError, CudaError, CudaResult, DropResult, ResultExt, ToResult};
+use crate::event::{Event, EventFlags};
+use crate::function::{BlockSize, Function, GridSize, KernelArg, LaunchConfig};
 use cuda_sys::cuda::{self, cudaError_t, CUstream};
 use std::ffi::c_void;
+use std::future::Future;
 use std::mem;
+use std::ops::Deref;
 use std::panic;
+use std::pin::Pin;
 use std::ptr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+
+// Special stream handles from cuda.h, which are not exported by cuda-sys.
+const CU_STREAM_LEGACY: CUstream = 0x1 as CUstream;
+const CU_STREAM_PER_THREAD: CUstream = 0x2 as CUstream;
 
 bitflags! {
     /// Bit flags for configuring a CUDA Stream.
@@ -32,9 +44,9 @@ bitflags! {
         /// regardless of the flag. However, for legacy reasons, CUDA has a notion of a NULL stream,
         /// which is used as the default when no other stream is provided. Work on other streams
         /// may not be executed concurrently with work on the NULL stream unless this flag is set.
-        /// Since RustaCUDA does not provide access to the NULL stream, this flag has no effect in
-        /// most circumstances. However, it is recommended to use it anyway, as some other crate
-        /// in this binary may be using the NULL stream directly.
+        /// RustaCUDA only provides access to the NULL stream through `Stream::legacy_default`, so
+        /// this flag has no effect in most circumstances. However, it is recommended to use it
+        /// anyway, as some other crate in this binary may be using the NULL stream directly.
         const NON_BLOCKING = 0x01;
     }
 }
@@ -87,6 +99,69 @@ impl Stream {
         }
     }
 
+    /// Returns a handle to the legacy default stream (also known as the NULL stream).
+    ///
+    /// Work queued on the legacy default stream waits for all work previously queued on blocking
+    /// streams in the same context, and work on blocking streams waits for it in turn. Streams
+    /// created with `StreamFlags::NON_BLOCKING` do not synchronize with it.
+    ///
+    /// The returned `Stream` does not own the underlying stream; dropping it does nothing.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::stream::Stream;
+    ///
+    /// let stream = Stream::legacy_default();
+    /// stream.synchronize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn legacy_default() -> Self {
+        Stream {
+            inner: CU_STREAM_LEGACY,
+        }
+    }
+
+    /// Returns a handle to the per-thread default stream of the calling host thread.
+    ///
+    /// The per-thread default stream does not synchronize with other streams, except for the
+    /// legacy default stream. This matches the semantics of libraries compiled with
+    /// per-thread default streams enabled (`--default-stream per-thread`).
+    ///
+    /// The returned `Stream` does not own the underlying stream; dropping it does nothing.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::stream::Stream;
+    ///
+    /// let stream = Stream::per_thread_default();
+    /// stream.synchronize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn per_thread_default() -> Self {
+        Stream {
+            inner: CU_STREAM_PER_THREAD,
+        }
+    }
+
+    // Returns true if this stream must be destroyed when dropped.
+    fn is_owned(&self) -> bool {
+        !(self.inner.is_null()
+            || self.inner == CU_STREAM_LEGACY
+            || self.inner == CU_STREAM_PER_THREAD)
+    }
+
     /// Return the flags which were used to create this stream.
     ///
     /// # Examples:
@@ -145,7 +220,14 @@ impl Stream {
     /// items in the stream have been completed. Subsequently queued
     /// items will not execute until the callback is finished.
     ///
-    /// Callbacks must not make any CUDA API calls.
+    /// Callbacks must not make any CUDA API calls, either directly or
+    /// indirectly (for example by dropping a `DeviceBuffer` or `Stream`).
+    /// Doing so may deadlock or return `CudaError::NotPermitted`. To free
+    /// CUDA resources from a callback, send them to another thread instead.
+    ///
+    /// The callback runs on a thread owned by the driver, so it must be
+    /// `Send` and `'static`. Panics inside the callback are caught and
+    /// discarded rather than unwinding into the driver.
     ///
     /// The callback will be passed a `CudaResult<()>` indicating the
     /// current state of the device with `Ok(())` denoting normal operation.
@@ -163,17 +245,19 @@ impl Stream {
     ///
     /// // ... queue up some work on the stream
     ///
-    /// stream.add_callback(Box::new(|status| {
+    /// stream.add_callback(|status| {
     ///     println!("Device status is {:?}", status);
-    /// }));
+    /// })?;
     ///
     /// // ... queue up some more work on the stream
     /// # Ok(())
     /// # }
-    pub fn add_callback<T>(&self, callback: Box<T>) -> CudaResult<()>
+    /// ```
+    pub fn add_callback<T>(&self, callback: T) -> CudaResult<()>
     where
-        T: FnOnce(CudaResult<()>) + Send,
+        T: FnOnce(CudaResult<()>) + Send + 'static,
     {
+        let callback = Box::new(callback);
         unsafe {
             cuda::cuStreamAddCallback(
                 self.inner,
@@ -185,6 +269,165 @@ impl Stream {
         }
     }
 
+    /// Check whether a stream's tasks are completed, without blocking.
+    ///
+    /// Returns `Ok(true)` if all operations scheduled for this stream have completed, and
+    /// `Ok(false)` if some are still pending.
+    ///
+    /// # Errors:
+    ///
+    /// If any of the work on the stream failed, returns the error from CUDA.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    ///
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    ///
+    /// // ... queue up some work on the stream
+    ///
+    /// while !stream.query()? {
+    ///     // ... do some work on the host while the device is busy
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query(&self) -> CudaResult<bool> {
+        unsafe {
+            match cuda::cuStreamQuery(self.inner).to_result() {
+                Ok(()) => Ok(true),
+                Err(CudaError::NotReady) => Ok(false),
+                Err(e) => Err(e),
+            }
+        }
+    }
+
+    /// Returns a future which resolves once all of the work currently queued on this stream has
+    /// completed.
+    ///
+    /// This lets asynchronous host code `.await` the device instead of blocking a thread in
+    /// [`synchronize`](#method.synchronize). The future is woken by a stream callback, so it does
+    /// not poll the device. Work queued on the stream after this call is not waited for.
+    ///
+    /// The future only relies on the standard `Waker` and is `Send`. It makes no CUDA calls when
+    /// polled or dropped, so it can be awaited from any executor, such as tokio or async-std,
+    /// including on a multi-threaded runtime whose threads don't have the context current. This
+    /// isn't true of futures created with
+    /// [`WakeStrategy::Poll`](enum.WakeStrategy.html#variant.Poll), which query an event when
+    /// polled and so need the stream's context to be current on every thread which polls them.
+    ///
+    /// # Errors:
+    ///
+    /// If the callback can't be added to the stream, returns the error from CUDA. The future
+    /// itself resolves to the error from any failed work on the stream.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # // A minimal stand-in for an executor's `block_on`.
+    /// # include!("test_util.rs");
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::error::CudaResult;
+    /// use rustacuda::memory::{AsyncCopyDestination, DeviceBuffer, LockedBuffer};
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    ///
+    /// async fn upload(
+    ///     stream: &Stream,
+    ///     host: &LockedBuffer<f32>,
+    ///     device: &mut DeviceBuffer<f32>,
+    /// ) -> CudaResult<()> {
+    ///     unsafe {
+    ///         device.async_copy_from(host, stream)?;
+    ///     }
+    ///     // The buffers stay borrowed until the copy has completed.
+    ///     stream.completion()?.await
+    /// }
+    ///
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let host = LockedBuffer::new(&1.0f32, 1024)?;
+    /// let mut device = DeviceBuffer::from_slice(&[0.0f32; 1024])?;
+    /// // With tokio, this would be `upload(&stream, &host, &mut device).await?` in an async fn.
+    /// block_on(upload(&stream, &host, &mut device))?;
+    /// assert_eq!(vec![1.0f32; 1024], device.copy_to_vec()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn completion(&self) -> CudaResult<StreamCompletion> {
+        self.completion_with(WakeStrategy::Callback)
+    }
+
+    /// Returns a future which resolves once all of the work currently queued on this stream has
+    /// completed, using the given strategy to decide when to wake the task awaiting it.
+    ///
+    /// See [`completion`](#method.completion) and [`WakeStrategy`](enum.WakeStrategy.html).
+    ///
+    /// # Errors:
+    ///
+    /// If the callback or event can't be added to the stream, returns the error from CUDA. The
+    /// future itself resolves to the error from any failed work on the stream.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::stream::{Stream, StreamFlags, WakeStrategy};
+    /// use std::time::Duration;
+    ///
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    ///
+    /// // ... queue up some work on the stream
+    ///
+    /// let strategy = WakeStrategy::Poll {
+    ///     interval: Duration::from_millis(1),
+    /// };
+    /// let done = stream.completion_with(strategy)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn completion_with(&self, strategy: WakeStrategy) -> CudaResult<StreamCompletion> {
+        match strategy {
+            WakeStrategy::Callback => {
+                let state = Arc::new(Mutex::new(CompletionState {
+                    result: None,
+                    waker: None,
+                }));
+                let callback_state = state.clone();
+                self.add_callback(move |result| {
+                    let mut state = callback_state.lock().unwrap();
+                    state.result = Some(result);
+                    if let Some(waker) = state.waker.take() {
+                        waker.wake();
+                    }
+                })?;
+                Ok(StreamCompletion {
+                    inner: CompletionInner::Callback(state),
+                })
+            }
+            WakeStrategy::Poll { interval } => {
+                let event = Event::new(EventFlags::DISABLE_TIMING)?;
+                event.record(self)?;
+                Ok(StreamCompletion {
+                    inner: CompletionInner::Poll {
+                        event: PollEvent(event),
+                        interval,
+                        timer: None,
+                    },
+                })
+            }
+        }
+    }
+
     /// Wait until a stream's tasks are completed.
     ///
     /// Waits until the device has completed all operations scheduled for this stream.
@@ -211,6 +454,52 @@ impl Stream {
         unsafe { cuda::cuStreamSynchronize(self.inner).to_result() }
     }
 
+    /// Make all future work submitted to this stream wait until `event` has completed.
+    ///
+    /// This allows work queued on different streams to be ordered without blocking the host. The
+    /// wait applies to the most recent call to `Event::record` on `event`; if the event has not
+    /// been recorded on any stream yet, this has no effect.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::event::{Event, EventFlags};
+    /// use rustacuda::stream::{Stream, StreamFlags};
+    ///
+    /// let producer = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let consumer = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// let event = Event::new(EventFlags::DISABLE_TIMING)?;
+    ///
+    /// // ... queue up some work on the producer stream
+    /// event.record(&producer)?;
+    ///
+    /// // Work queued on the consumer stream after this point will not start until the
+    /// // producer's work has completed.
+    /// consumer.wait_event(&event)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_event(&self, event: &Event) -> CudaResult<()> {
+        unsafe { cuda::cuStreamWaitEvent(self.inner, event.as_inner(), 0).to_result() }
+    }
+
+    pub(crate) fn as_inner(&self) -> CUstream {
+        self.inner
+    }
+
+    // Called after queueing asynchronous work to implement `set_force_synchronous`.
+    pub(crate) fn sync_if_forced(&self) -> CudaResult<()> {
+        if crate::is_force_synchronous() {
+            self.synchronize()
+        } else {
+            Ok(())
+        }
+    }
+
     // Hidden implementation detail function. Highly unsafe. Use the `launch!` macro instead.
     #[doc(hidden)]
     pub unsafe fn launch<G, B>(
@@ -241,7 +530,109 @@ impl Stream {
             args.as_ptr() as *mut _,
             ptr::null_mut(),
         )
-        .to_result()
+        .to_result()?;
+        self.sync_if_forced()
+    }
+
+    /// Enqueue several kernel launches on this stream, one after another.
+    ///
+    /// Each launch is given as a function, its launch configuration and its arguments. No dynamic
+    /// shared memory is allocated for any of the launches. The launches are enqueued in order;
+    /// if one fails to enqueue, the remaining launches are skipped and the index of the failed
+    /// launch is returned along with the error. Launches before the failed one have already been
+    /// enqueued and will still run.
+    ///
+    /// The launch configurations aren't validated against the device's limits, to keep the batch
+    /// cheap to enqueue. Check them once with
+    /// [`LaunchConfig::validate`](../function/struct.LaunchConfig.html#method.validate) beforehand if
+    /// they aren't known to be valid.
+    ///
+    /// # Safety
+    ///
+    /// As with [`launch!`](../macro.launch.html), the caller must ensure that the arguments match
+    /// the parameters each kernel expects and that any memory the kernels access remains valid
+    /// until they complete.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::function::{KernelArg, LaunchConfig};
+    /// use rustacuda::memory::*;
+    /// use rustacuda::module::Module;
+    /// use rustacuda::stream::*;
+    /// use std::ffi::CString;
+    ///
+    /// let ptx = CString::new(include_str!("../resources/add.ptx"))?;
+    /// let module = Module::load_from_string(&ptx)?;
+    /// let sum = module.get_function(&CString::new("sum")?)?;
+    /// let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    ///
+    /// let mut x = DeviceBuffer::from_slice(&[1.0f32; 10])?;
+    /// let mut y = DeviceBuffer::from_slice(&[2.0f32; 10])?;
+    /// let mut out_1 = DeviceBuffer::from_slice(&[0.0f32; 10])?;
+    /// let mut out_2 = DeviceBuffer::from_slice(&[0.0f32; 10])?;
+    /// let (x, y, out_1, out_2) = (
+    ///     x.as_device_ptr(),
+    ///     y.as_device_ptr(),
+    ///     out_1.as_device_ptr(),
+    ///     out_2.as_device_ptr(),
+    /// );
+    /// let count = 10i32;
+    /// let config = sum.suggest_launch(10)?;
+    /// let args_1: [&dyn KernelArg; 4] = [&x, &y, &out_1, &count];
+    /// let args_2: [&dyn KernelArg; 4] = [&x, &y, &out_2, &count];
+    ///
+    /// unsafe {
+    ///     stream
+    ///         .launch_batch(&[
+    ///             (&sum, config.clone(), &args_1[..]),
+    ///             (&sum, config, &args_2[..]),
+    ///         ])
+    ///         .map_err(|(_, e)| e)?;
+    /// }
+    /// stream.synchronize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn launch_batch(
+        &self,
+        launches: &[(&Function, LaunchConfig, &[&dyn KernelArg])],
+    ) -> Result<(), (usize, CudaError)> {
+        let mut params = Vec::new();
+        for (i, &(func, ref config, args)) in launches.iter().enumerate() {
+            params.clear();
+            params.extend(args.iter().map(|arg| arg.as_kernel_param()));
+            self.launch(func, &config.grid, &config.block, 0, &params)
+                .map_err(|e| (i, e))?;
+        }
+        Ok(())
+    }
+
+    // Hidden implementation detail function. Highly unsafe. Use the `launch_checked!` macro
+    // instead.
+    #[doc(hidden)]
+    pub unsafe fn launch_checked<G, B>(
+        &self,
+        func: &Function,
+        grid_size: G,
+        block_size: B,
+        shared_mem_bytes: u32,
+        args: &[*mut c_void],
+    ) -> Result<(), ContextError>
+    where
+        G: Into<GridSize>,
+        B: Into<BlockSize>,
+    {
+        let grid_size: GridSize = grid_size.into();
+        let block_size: BlockSize = block_size.into();
+
+        func.check_launch_dims(&grid_size, &block_size, shared_mem_bytes)?;
+        self.launch(func, grid_size, block_size, shared_mem_bytes, args)
+            .context("launching the kernel")
     }
 
     /// Destroy a `Stream`, returning an error.
@@ -270,7 +661,8 @@ impl Stream {
     /// # }
     /// ```
     pub fn drop(mut stream: Stream) -> DropResult<Stream> {
-        if stream.inner.is_null() {
+        if !stream.is_owned() {
+            mem::forget(stream);
             return Ok(());
         }
 
@@ -288,7 +680,7 @@ impl Stream {
 }
 impl Drop for Stream {
     fn drop(&mut self) {
-        if self.inner.is_null() {
+        if !self.is_owned() {
             return;
         }
 
@@ -301,12 +693,238 @@ impl Drop for Stream {
         }
     }
 }
+
+/// A stream which waits for all of its queued work to complete before it is destroyed.
+///
+/// A plain [`Stream`](struct.Stream.html) is destroyed as soon as it is dropped, even if work
+/// such as an asynchronous copy is still pending. The work still completes, but nothing stops the
+/// memory it reads or writes from being freed or reused in the meantime. A `SyncStream`
+/// synchronizes in its destructor, so that once it has been dropped, all of its work has
+/// finished with the memory it was given.
+///
+/// Locals are dropped in the reverse of the order they were declared in, so the buffers used by
+/// the stream's work must be declared before the `SyncStream` for them to outlive it.
+///
+/// Synchronizing blocks the dropping thread until the stream is idle, which stalls the host if
+/// the stream has a lot of work queued. For that reason `Stream` does not do this by default;
+/// prefer synchronizing explicitly in performance-sensitive code, and use `SyncStream` where the
+/// safety net is worth the wait.
+///
+/// `SyncStream` dereferences to `Stream`, so it can be used anywhere a `&Stream` is expected.
+///
+/// # Examples:
+///
+/// ```
+/// # use rustacuda::*;
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # let _ctx = quick_init()?;
+/// use rustacuda::memory::*;
+/// use rustacuda::stream::{StreamFlags, SyncStream};
+///
+/// let device = DeviceBuffer::from_slice(&[1u32, 2, 3])?;
+/// let mut host = LockedBuffer::new(&0u32, 3)?;
+/// {
+///     let stream = SyncStream::new(StreamFlags::NON_BLOCKING, None)?;
+///     unsafe { device.async_copy_to(&mut host, &stream)? };
+///     // The stream is synchronized here, before it is destroyed.
+/// }
+/// assert_eq!(&[1, 2, 3], host.as_slice());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SyncStream {
+    stream: Stream,
+}
+impl SyncStream {
+    /// Create a new stream with the given flags and optional priority, which synchronizes before
+    /// it is destroyed.
+    ///
+    /// See [`Stream::new`](struct.Stream.html#method.new) for details of the arguments.
+    ///
+    /// # Errors:
+    ///
+    /// If creating the stream fails, returns the error from CUDA.
+    pub fn new(flags: StreamFlags, priority: Option<i32>) -> CudaResult<Self> {
+        Stream::new(flags, priority).map(SyncStream::from)
+    }
+
+    /// Return the underlying `Stream` without synchronizing it. The returned stream will no longer
+    /// synchronize when it is dropped.
+    pub fn into_inner(self) -> Stream {
+        let stream = unsafe { ptr::read(&self.stream) };
+        mem::forget(self);
+        stream
+    }
+
+    /// Synchronize and destroy a `SyncStream`, returning an error.
+    ///
+    /// Synchronizing or destroying a stream can return errors from previous asynchronous work.
+    /// This function returns the error and the un-destroyed stream on failure.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # use rustacuda::*;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let _ctx = quick_init()?;
+    /// use rustacuda::stream::{StreamFlags, SyncStream};
+    ///
+    /// let stream = SyncStream::new(StreamFlags::NON_BLOCKING, None)?;
+    /// match SyncStream::drop(stream) {
+    ///     Ok(()) => println!("Successfully destroyed"),
+    ///     Err((e, stream)) => {
+    ///         println!("Failed to destroy stream: {:?}", e);
+    ///         // Do something with stream
+    ///     },
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn drop(stream: SyncStream) -> DropResult<SyncStream> {
+        if let Err(e) = stream.synchronize() {
+            return Err((e, stream));
+        }
+        Stream::drop(stream.into_inner()).map_err(|(e, stream)| (e, SyncStream::from(stream)))
+    }
+}
+impl From<Stream> for SyncStream {
+    fn from(stream: Stream) -> Self {
+        SyncStream { stream }
+    }
+}
+impl Deref for SyncStream {
+    type Target = Stream;
+
+    fn deref(&self) -> &Stream {
+        &self.stream
+    }
+}
+impl Drop for SyncStream {
+    fn drop(&mut self) {
+        // No choice but to panic here.
+        self.stream
+            .synchronize()
+            .expect("Failed to synchronize CUDA stream.");
+    }
+}
+
+/// How a [`StreamCompletion`](struct.StreamCompletion.html) future finds out that the stream's
+/// work has completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeStrategy {
+    /// Add a callback to the stream which wakes the task awaiting the future. The callback runs
+    /// on a thread owned by the CUDA driver. This is the default.
+    Callback,
+    /// Record an event on the stream, and check it each time the future is polled. While the work
+    /// is pending, a single timer thread started by the first poll wakes the task every
+    /// `interval`. The timer thread only wakes the task and never calls into CUDA, so it exits
+    /// once the future resolves or is dropped.
+    ///
+    /// The stream's context must be current on any thread which polls or drops the future.
+    Poll {
+        /// Time to wait between checks.
+        interval: Duration,
+    },
+}
+
+/// Future which resolves once the work queued on a stream before it was created has completed.
+///
+/// This is created by [`Stream::completion`](struct.Stream.html#method.completion),
+/// [`Stream::completion_with`](struct.Stream.html#method.completion_with) and
+/// [`Function::launch_async`](../function/struct.Function.html#method.launch_async).
+#[derive(Debug)]
+pub struct StreamCompletion {
+    inner: CompletionInner,
+}
+#[derive(Debug)]
+enum CompletionInner {
+    Callback(Arc<Mutex<CompletionState>>),
+    Poll {
+        event: PollEvent,
+        interval: Duration,
+        // Waker for the timer thread to wake, shared with it once the first poll has started it.
+        timer: Option<Arc<Mutex<Option<Waker>>>>,
+    },
+}
+#[derive(Debug)]
+struct CompletionState {
+    result: Option<CudaResult<()>>,
+    waker: Option<Waker>,
+}
+// The event recorded for `WakeStrategy::Poll`, which is the only part of a `StreamCompletion` that
+// isn't `Send` by itself. Driver handles aren't tied to the thread which created them and the
+// driver API is thread-safe, so the event can be queried and destroyed from whichever thread the
+// executor moves the future to. Those calls do depend on the event's context being current on that
+// thread, which is documented on `WakeStrategy::Poll`. Nothing else is done with the event.
+#[derive(Debug)]
+struct PollEvent(Event);
+unsafe impl Send for PollEvent {}
+
+// Wake the task every `interval` until the future holding the other reference to `waker` resolves
+// or is dropped.
+fn spawn_poll_timer(waker: Arc<Mutex<Option<Waker>>>, interval: Duration) {
+    drop(thread::spawn(move || loop {
+        thread::sleep(interval);
+        if Arc::strong_count(&waker) == 1 {
+            break;
+        }
+        // Take the waker so that an idle future isn't woken again until it has been re-polled.
+        let waker = waker.lock().unwrap().take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }));
+}
+
+impl Future for StreamCompletion {
+    type Output = CudaResult<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<CudaResult<()>> {
+        match self.get_mut().inner {
+            CompletionInner::Callback(ref state) => {
+                let mut state = state.lock().unwrap();
+                match state.result.take() {
+                    Some(result) => Poll::Ready(result),
+                    None => {
+                        state.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            }
+            CompletionInner::Poll {
+                ref event,
+                interval,
+                ref mut timer,
+            } => match event.0.query() {
+                Ok(false) => {
+                    // Spurious polls only replace the waker; there's one timer thread per future.
+                    let timer = timer.get_or_insert_with(|| {
+                        let timer = Arc::new(Mutex::new(None));
+                        spawn_poll_timer(timer.clone(), interval);
+                        timer
+                    });
+                    *timer.lock().unwrap() = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+                result => {
+                    // Let the timer thread exit.
+                    *timer = None;
+                    Poll::Ready(result.map(|_| ()))
+                }
+            },
+        }
+    }
+}
+
 unsafe extern "C" fn callback_wrapper<T>(
     _stream: CUstream,
     status: cudaError_t,
     callback: *mut c_void,
 ) where
-    T: FnOnce(CudaResult<()>) + Send,
+    T: FnOnce(CudaResult<()>) + Send + 'static,
 {
     // Stop panics from unwinding across the FFI
     let _ = panic::catch_unwind(|| {
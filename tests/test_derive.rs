@@ -16,6 +16,12 @@ struct NormalStruct {
     y: u64,
 }
 
+#[derive(Clone, DeviceCopy)]
+struct MixedPrimitiveStruct {
+    x: f32,
+    y: u32,
+}
+
 #[derive(Clone, DeviceCopy)]
 struct ContainerStruct {
     a: NormalStruct,
@@ -48,11 +54,31 @@ union TestUnion {
     i: i64,
 }
 
+#[derive(Clone, DeviceCopy, Zeroable)]
+struct ZeroableStruct {
+    x: u64,
+    y: f32,
+    z: [u8; 4],
+}
+
+#[derive(Clone, DeviceCopy, Zeroable)]
+struct GenericZeroableStruct<T> {
+    value: T,
+}
+
+#[derive(Copy, Clone, DeviceCopy, Zeroable)]
+#[repr(C)]
+union ZeroableUnion {
+    u: u64,
+    f: f64,
+}
+
 #[test]
 fn test_hidden_functions() {
     __verify_ZeroSizedStruct_can_implement_DeviceCopy(&ZeroSizedStruct);
     __verify_TupleStruct_can_implement_DeviceCopy(&TupleStruct(0, 0));
     __verify_NormalStruct_can_implement_DeviceCopy(&NormalStruct { x: 0, y: 0 });
+    __verify_MixedPrimitiveStruct_can_implement_DeviceCopy(&MixedPrimitiveStruct { x: 0.0, y: 0 });
     __verify_ContainerStruct_can_implement_DeviceCopy(&ContainerStruct {
         a: NormalStruct { x: 0, y: 0 },
         b: TupleStruct(0, 0),
@@ -61,4 +87,28 @@ fn test_hidden_functions() {
     __verify_TestEnum_can_implement_DeviceCopy(&TestEnum::Unit);
     __verify_GenericEnum_can_implement_DeviceCopy::<u64>(&GenericEnum::Unit);
     __verify_TestUnion_can_implement_DeviceCopy(&TestUnion { u: 0u64 });
+    __verify_ZeroableStruct_can_implement_Zeroable(&ZeroableStruct {
+        x: 0,
+        y: 0.0,
+        z: [0; 4],
+    });
+    __verify_GenericZeroableStruct_can_implement_Zeroable(&GenericZeroableStruct { value: 0u64 });
+    __verify_ZeroableUnion_can_implement_Zeroable(&ZeroableUnion { u: 0u64 });
+}
+
+#[test]
+fn test_zeroed_derived_struct() {
+    use rustacuda::memory::{CopyDestination, DeviceBox};
+
+    let _context = rustacuda::quick_init().unwrap();
+    let device_box = DeviceBox::<ZeroableStruct>::zeroed().unwrap();
+    let mut host = ZeroableStruct {
+        x: 1,
+        y: 2.0,
+        z: [3; 4],
+    };
+    device_box.copy_to(&mut host).unwrap();
+    assert_eq!(0, host.x);
+    assert_eq!(0.0, host.y);
+    assert_eq!([0; 4], host.z);
 }
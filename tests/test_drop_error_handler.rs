@@ -0,0 +1,34 @@
+extern crate rustacuda;
+
+// `set_drop_error_handler` is global to the process, so this test has its own binary to keep the
+// handler from being replaced by, or seeing the errors of, tests running in parallel with it.
+
+use rustacuda::memory::{set_drop_error_handler, UnifiedBuffer};
+use rustacuda::quick_init;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static HANDLED: AtomicBool = AtomicBool::new(false);
+
+// Uninstalls the handler when dropped, so a failing assertion can't leave it installed.
+struct HandlerGuard;
+
+impl Drop for HandlerGuard {
+    fn drop(&mut self) {
+        set_drop_error_handler(None);
+    }
+}
+
+#[test]
+fn test_drop_error_goes_to_handler() {
+    let context = quick_init().unwrap();
+    let mut buffer = UnifiedBuffer::new(&0u64, 16).unwrap();
+    buffer.set_panic_on_drop_error(false);
+
+    // Destroying the context frees the buffer's memory, so the buffer can't free it again.
+    drop(context);
+
+    let _guard = HandlerGuard;
+    set_drop_error_handler(Some(|_| HANDLED.store(true, Ordering::SeqCst)));
+    drop(buffer);
+    assert!(HANDLED.load(Ordering::SeqCst));
+}
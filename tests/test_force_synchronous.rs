@@ -0,0 +1,31 @@
+extern crate rustacuda;
+
+// `set_force_synchronous` is global to the process, so this test has its own binary to keep it
+// from synchronizing the asynchronous work of tests running in parallel with it.
+
+use rustacuda::memory::{AsyncCopyDestination, LockedBuffer};
+use rustacuda::prelude::*;
+use rustacuda::quick_init;
+
+#[test]
+fn test_force_synchronous() {
+    let _ctx = quick_init();
+    let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+
+    let source = LockedBuffer::from_slice(&[1u32, 2, 3, 4]).unwrap();
+    let mut device = DeviceBuffer::from_slice(&[0u32; 4]).unwrap();
+    let mut result = LockedBuffer::new(&0u32, 4).unwrap();
+
+    rustacuda::set_force_synchronous(true);
+    assert!(rustacuda::is_force_synchronous());
+    unsafe {
+        device.async_copy_from(&source, &stream).unwrap();
+        device.async_copy_to(&mut result, &stream).unwrap();
+    }
+    // No synchronize; the copies must already have completed.
+    assert!(stream.query().unwrap());
+    assert_eq!(source.as_slice(), result.as_slice());
+
+    rustacuda::set_force_synchronous(false);
+    assert!(!rustacuda::is_force_synchronous());
+}
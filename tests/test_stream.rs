@@ -1,8 +1,22 @@
 extern crate rustacuda;
 
+#[path = "../src/test_util.rs"]
+mod test_util;
+
+use rustacuda::context::CurrentContext;
+use rustacuda::event::{Event, EventFlags};
+use rustacuda::memory::{AsyncCopyDestination, LockedBuffer};
 use rustacuda::prelude::*;
 use rustacuda::quick_init;
+use rustacuda::stream::{SyncStream, WakeStrategy};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::sync_channel;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread;
+use std::time::Duration;
+use test_util::block_on;
 
 #[test]
 fn test_stream_callbacks_execution_order() {
@@ -11,17 +25,23 @@ fn test_stream_callbacks_execution_order() {
 
     let (order_sender, order_receiver) = sync_channel(0);
     stream
-        .add_callback(Box::new(|_| {
-            order_sender.send(1).unwrap();
+        .add_callback(Box::new({
+            let order_sender = order_sender.clone();
+            move |_| {
+                order_sender.send(1).unwrap();
+            }
         }))
         .unwrap();
     stream
-        .add_callback(Box::new(|_| {
-            order_sender.send(2).unwrap();
+        .add_callback(Box::new({
+            let order_sender = order_sender.clone();
+            move |_| {
+                order_sender.send(2).unwrap();
+            }
         }))
         .unwrap();
     stream
-        .add_callback(Box::new(|_| {
+        .add_callback(Box::new(move |_| {
             order_sender.send(3).unwrap();
         }))
         .unwrap();
@@ -38,7 +58,7 @@ fn test_stream_callbacks_environment_capture() {
     let (capture_sender, capture_receiver) = sync_channel(0);
     let magic_numbers = (42, Box::new(1337));
     stream
-        .add_callback(Box::new(|_| {
+        .add_callback(Box::new(move |_| {
             capture_sender.send(magic_numbers).unwrap();
         }))
         .unwrap();
@@ -54,9 +74,227 @@ fn test_stream_callbacks_status_propagation() {
 
     let (status_sender, status_receiver) = sync_channel(0);
     stream
-        .add_callback(Box::new(|status| {
+        .add_callback(Box::new(move |status| {
             status_sender.send(status).unwrap();
         }))
         .unwrap();
     assert_eq!(Ok(()), status_receiver.recv().unwrap())
 }
+
+#[test]
+fn test_stream_callbacks_unboxed() {
+    let _ctx = quick_init();
+    let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+
+    let flag = Arc::new(AtomicBool::new(false));
+    let callback_flag = flag.clone();
+    stream
+        .add_callback(move |status| {
+            assert_eq!(Ok(()), status);
+            callback_flag.store(true, Ordering::SeqCst);
+        })
+        .unwrap();
+    stream.synchronize().unwrap();
+    assert!(flag.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_stream_wait_event() {
+    let _ctx = quick_init();
+    let producer = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+    let consumer = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+    let event = Event::new(EventFlags::DISABLE_TIMING).unwrap();
+
+    let source = LockedBuffer::from_slice(&[1u32, 2, 3, 4]).unwrap();
+    let mut device = DeviceBuffer::from_slice(&[0u32; 4]).unwrap();
+    let mut result = LockedBuffer::new(&0u32, 4).unwrap();
+    unsafe {
+        device.async_copy_from(&source, &producer).unwrap();
+        event.record(&producer).unwrap();
+        consumer.wait_event(&event).unwrap();
+        device.async_copy_to(&mut result, &consumer).unwrap();
+    }
+    consumer.synchronize().unwrap();
+    assert_eq!(source.as_slice(), result.as_slice());
+}
+
+#[test]
+fn test_default_streams() {
+    let _ctx = quick_init();
+    let source = LockedBuffer::from_slice(&[1u32, 2, 3, 4]).unwrap();
+
+    for stream in [Stream::legacy_default(), Stream::per_thread_default()] {
+        let mut device = DeviceBuffer::from_slice(&[0u32; 4]).unwrap();
+        let mut result = LockedBuffer::new(&0u32, 4).unwrap();
+        unsafe {
+            device.async_copy_from(&source, &stream).unwrap();
+            device.async_copy_to(&mut result, &stream).unwrap();
+        }
+        stream.synchronize().unwrap();
+        assert_eq!(source.as_slice(), result.as_slice());
+        drop(stream);
+    }
+
+    // Dropping the default streams must not have destroyed them.
+    Stream::legacy_default().synchronize().unwrap();
+    Stream::per_thread_default().synchronize().unwrap();
+    Stream::drop(Stream::legacy_default()).unwrap();
+}
+
+#[test]
+fn test_stream_query() {
+    let _ctx = quick_init();
+    let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+
+    let source = LockedBuffer::from_slice(&[1u32, 2, 3, 4]).unwrap();
+    let mut device = DeviceBuffer::from_slice(&[0u32; 4]).unwrap();
+    let mut result = LockedBuffer::new(&0u32, 4).unwrap();
+    unsafe {
+        device.async_copy_from(&source, &stream).unwrap();
+        device.async_copy_to(&mut result, &stream).unwrap();
+    }
+
+    while !stream.query().unwrap() {
+        std::thread::yield_now();
+    }
+    assert_eq!(source.as_slice(), result.as_slice());
+}
+
+#[test]
+fn test_stream_priority_range_ends() {
+    let _ctx = quick_init();
+    let range = CurrentContext::get_stream_priority_range().unwrap();
+    assert!(range.greatest <= range.least);
+
+    let high = Stream::new(StreamFlags::NON_BLOCKING, Some(range.greatest)).unwrap();
+    let low = Stream::new(StreamFlags::NON_BLOCKING, Some(range.least)).unwrap();
+    assert_eq!(range.greatest, high.get_priority().unwrap());
+    assert_eq!(range.least, low.get_priority().unwrap());
+
+    // Out-of-range priorities are clamped to the nearest end of the range.
+    let clamped = Stream::new(StreamFlags::NON_BLOCKING, Some(range.greatest - 1)).unwrap();
+    assert_eq!(range.greatest, clamped.get_priority().unwrap());
+}
+
+#[test]
+fn test_context_synchronize_drains_all_streams() {
+    let _ctx = quick_init();
+    let streams: Vec<Stream> = (0..4)
+        .map(|_| Stream::new(StreamFlags::NON_BLOCKING, None).unwrap())
+        .collect();
+
+    let source = LockedBuffer::from_slice(&[1u32, 2, 3, 4]).unwrap();
+    let mut devices: Vec<DeviceBuffer<u32>> = streams
+        .iter()
+        .map(|_| DeviceBuffer::from_slice(&[0u32; 4]).unwrap())
+        .collect();
+    let mut results: Vec<LockedBuffer<u32>> = streams
+        .iter()
+        .map(|_| LockedBuffer::new(&0u32, 4).unwrap())
+        .collect();
+    for ((stream, device), result) in streams.iter().zip(&mut devices).zip(&mut results) {
+        unsafe {
+            device.async_copy_from(&source, stream).unwrap();
+            device.async_copy_to(result, stream).unwrap();
+        }
+    }
+
+    CurrentContext::synchronize().unwrap();
+    for (stream, result) in streams.iter().zip(&results) {
+        assert!(stream.query().unwrap());
+        assert_eq!(source.as_slice(), result.as_slice());
+    }
+}
+
+#[test]
+fn test_sync_stream_waits_on_drop() {
+    let _ctx = quick_init();
+    let source: Vec<u32> = (0..(1 << 22)).collect();
+    let device = DeviceBuffer::from_slice(&source).unwrap();
+    let mut result = LockedBuffer::new(&0u32, source.len()).unwrap();
+    {
+        let stream = SyncStream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+        // Hold up the stream so the copy is still pending when it is dropped.
+        stream
+            .add_callback(|_| thread::sleep(Duration::from_millis(100)))
+            .unwrap();
+        unsafe {
+            device.async_copy_to(&mut result, &stream).unwrap();
+        }
+        assert!(!stream.query().unwrap());
+    }
+    assert_eq!(source.as_slice(), result.as_slice());
+}
+
+#[test]
+fn test_await_host_to_device() {
+    let _ctx = quick_init();
+    let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+    let source = LockedBuffer::from_slice(&[1u64, 2, 3, 4]).unwrap();
+    let mut device = DeviceBuffer::from_slice(&[0u64; 4]).unwrap();
+    unsafe {
+        device.async_copy_from(&source, &stream).unwrap();
+    }
+    block_on(stream.completion().unwrap()).unwrap();
+
+    let mut host = [0u64; 4];
+    device.copy_to(&mut host).unwrap();
+    assert_eq!(source.as_slice(), &host);
+}
+
+#[test]
+fn test_await_roundtrip() {
+    let _ctx = quick_init();
+    let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+    let source = LockedBuffer::from_slice(&[1.0f32, 2.0, 3.0, 4.0]).unwrap();
+    let mut device = DeviceBuffer::from_slice(&[0.0f32; 4]).unwrap();
+    let mut result = LockedBuffer::new(&0.0f32, 4).unwrap();
+    for strategy in &[
+        WakeStrategy::Callback,
+        WakeStrategy::Poll {
+            interval: Duration::from_millis(1),
+        },
+    ] {
+        unsafe {
+            device.async_copy_from(&source, &stream).unwrap();
+            device.async_copy_to(&mut result, &stream).unwrap();
+        }
+        block_on(stream.completion_with(*strategy).unwrap()).unwrap();
+        assert_eq!(source.as_slice(), result.as_slice());
+    }
+}
+
+#[test]
+fn test_poll_completion_spurious_polls() {
+    struct NoopWaker;
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+    fn assert_send<T: Send>(_: &T) {}
+
+    let _ctx = quick_init();
+    let stream = Stream::new(StreamFlags::NON_BLOCKING, None).unwrap();
+    let source = LockedBuffer::new(&1u8, 1 << 24).unwrap();
+    let mut device = DeviceBuffer::from_slice(&vec![0u8; 1 << 24]).unwrap();
+    unsafe {
+        device.async_copy_from(&source, &stream).unwrap();
+    }
+    let future = stream
+        .completion_with(WakeStrategy::Poll {
+            interval: Duration::from_millis(1),
+        })
+        .unwrap();
+    assert_send(&future);
+
+    // Poll without waiting to be woken, as a busy executor might.
+    let mut future = Box::pin(future);
+    let waker = Arc::new(NoopWaker).into();
+    let mut cx = Context::from_waker(&waker);
+    let result = loop {
+        if let Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+    };
+    result.unwrap();
+    assert_eq!(vec![1u8; 1 << 24], device.copy_to_vec().unwrap());
+}